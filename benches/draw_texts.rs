@@ -0,0 +1,55 @@
+//! Compares batched [`SkiaBackend::draw_texts`] against one `draw_text` call per label for
+//! the tick labels of a typical axis.
+//!
+//! Run with `cargo bench --bench draw_texts`.
+
+use std::time::{Duration, Instant};
+
+use plotters::style::{IntoFont, TextStyle};
+use plotters_backend::DrawingBackend;
+use plotters_skia::SkiaSurfaceBackend;
+
+const ITERATIONS: u32 = 200;
+const FONT: &[u8] = include_bytes!("../tests/fonts/DejaVuSansMono.ttf");
+
+fn main() {
+    let labels: Vec<_> = (0..20).map(|i| (i * 25).to_string()).collect();
+    let items: Vec<_> = labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| ((40 + i as i32 * 36, 580), label.as_str()))
+        .collect();
+
+    let style = TextStyle::from(("DejaVu Sans Mono", 14).into_font());
+    let mut surface = SkiaSurfaceBackend::new(800, 600).unwrap();
+
+    // Bundled so the benchmark doesn't depend on the system's fonts
+    let mut backend = surface.backend();
+    backend.register_typeface(FONT).unwrap();
+
+    let individual = time(|| {
+        for &(pos, text) in &items {
+            backend.draw_text(text, &style, pos).unwrap();
+        }
+    });
+
+    let batched = time(|| {
+        backend.draw_texts(&items, &style).unwrap();
+    });
+
+    println!("individual draw_text: {:?} per axis", individual);
+    println!("draw_texts:           {:?} per axis", batched);
+}
+
+fn time(mut f: impl FnMut()) -> Duration {
+    // Warm up font and glyph caches
+    f();
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    start.elapsed() / ITERATIONS
+}
@@ -1,113 +1,3031 @@
 use std::{
+    collections::HashMap,
     error::Error as StdError,
     fmt::{Debug, Display, Formatter, Result as FmtResult},
+    io,
 };
 
 use plotters_backend::{
-    BackendColor, BackendCoord, BackendStyle, DrawingBackend, DrawingErrorKind,
+    text_anchor::{HPos, Pos, VPos},
+    BackendColor, BackendCoord, BackendStyle, BackendTextStyle, DrawingBackend, DrawingErrorKind,
+    FontStyle as PFontStyle, FontTransform,
+};
+#[cfg(feature = "shaping")]
+use skia_safe::textlayout::{
+    FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, TextStyle,
 };
 use skia_safe::{
-    images, AlphaType, BlendMode, Canvas, Color, ColorType, Data, ImageInfo, Paint, PaintStyle,
-    Path, Rect,
+    canvas::PointMode,
+    font::Edging,
+    font_style::{Slant, Weight, Width},
+    gradient_shader::Flags as GradientFlags,
+    image_filters, images,
+    utils::parse_path,
+    AlphaType, BlendMode, BlurStyle, Canvas, ClipOp, Color, ColorType, Data, FilterMode, Font,
+    FontMgr, FontStyle, Image, ImageInfo, MaskFilter, Matrix, MipmapMode, Paint, PaintCap,
+    PaintJoin, PaintStyle, Path, PathDirection, PathEffect, PathFillType, Picture, PictureRecorder,
+    Point, RRect, Rect, SamplingOptions, Shader, Surface, TileMode, Typeface,
 };
 
-pub struct SkiaBackend<'a> {
-    canvas: &'a mut Canvas,
-    width: u32,
-    height: u32,
-    blend_mode: Option<BlendMode>,
-}
+use crate::NumberFormat;
+
+// plotters' own TTF text rendering uses an em of `size / 1.24`
+const FONT_EM_FACTOR: f32 = 1.0 / 1.24;
+
+pub struct SkiaBackend<'a> {
+    target: Target<'a>,
+    width: u32,
+    height: u32,
+    style: StyleState,
+    saved_styles: Vec<StyleState>,
+    reset_on_present: bool,
+    dither: bool,
+    rounding: RoundingMode,
+    line_gamma: f32,
+    premul_gradients: bool,
+    bitmap_alpha_type: AlphaType,
+    bitmap_color_type: ColorType,
+    clip_depth: usize,
+    plot_area: Option<(BackendCoord, BackendCoord)>,
+    // Clip depth below the plot area clip while it is applied
+    plot_clip_level: Option<usize>,
+    anti_alias: bool,
+    aa_method: AaMethod,
+    typefaces: Vec<Typeface>,
+    shadow_color: BackendColor,
+    // plotters' fallbacks draw long runs of equally colored pixels so reuse their paint
+    pixel_paint: Option<(PixelPaintKey, Paint)>,
+    line_clip_bounds: Option<Rect>,
+    tee: Option<&'a mut PictureRecorder>,
+    #[cfg(feature = "shaping")]
+    font_collection: Option<FontCollection>,
+    #[cfg(feature = "shaping")]
+    tabular_figures: bool,
+}
+
+enum Target<'a> {
+    Canvas(&'a mut Canvas),
+    // Kept to flush it on present
+    Surface(&'a mut Surface),
+}
+
+impl Target<'_> {
+    fn canvas(&mut self) -> &mut Canvas {
+        match self {
+            Target::Canvas(canvas) => canvas,
+            Target::Surface(surface) => surface.canvas(),
+        }
+    }
+
+    fn canvas_ref(&self) -> Option<&Canvas> {
+        match self {
+            Target::Canvas(canvas) => Some(canvas),
+            Target::Surface(_) => None,
+        }
+    }
+}
+
+// Transient styling applied on top of each draw call's own style
+#[derive(Clone)]
+struct StyleState {
+    blend_mode: Option<BlendMode>,
+    dash: Option<Dash>,
+    dash_scale: f32,
+    line_cap: Option<PaintCap>,
+    line_join: Option<PaintJoin>,
+    fill_gradient: Option<LinearGradient>,
+}
+
+impl Default for StyleState {
+    fn default() -> Self {
+        Self {
+            blend_mode: None,
+            dash: None,
+            dash_scale: 1.0,
+            line_cap: None,
+            line_join: None,
+            fill_gradient: None,
+        }
+    }
+}
+
+// Everything the `draw_pixel` paint depends on
+#[derive(PartialEq)]
+struct PixelPaintKey {
+    color: Color,
+    blend_mode: Option<BlendMode>,
+    dither: bool,
+}
+
+// Built into a shader per draw so it picks up the current gradient flags
+#[derive(Clone)]
+struct LinearGradient {
+    points: (Point, Point),
+    positions: Vec<f32>,
+    colors: Vec<Color>,
+}
+
+impl LinearGradient {
+    fn shader(&self, flags: GradientFlags) -> Option<Shader> {
+        Shader::linear_gradient(
+            self.points,
+            self.colors.as_slice(),
+            self.positions.as_slice(),
+            TileMode::Clamp,
+            flags,
+            None,
+        )
+    }
+}
+
+#[derive(Clone)]
+struct Dash {
+    intervals: Vec<f32>,
+    phase: f32,
+    relative: bool,
+}
+
+impl Dash {
+    fn path_effect(&self, stroke_width: f32, scale: f32) -> Option<PathEffect> {
+        // Hairlines (width 0) still get dashes one pixel wide
+        let factor = if self.relative {
+            stroke_width.max(1.0) * scale
+        } else {
+            scale
+        };
+
+        let intervals: Vec<_> = self.intervals.iter().map(|i| i * factor).collect();
+
+        PathEffect::dash(&intervals, self.phase * factor)
+    }
+}
+
+/// Anti-aliasing technique of a [`SkiaBackend`], see [`SkiaBackend::set_aa_method`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AaMethod {
+    /// Skia computes the exact coverage of every edge pixel. Thin diagonal lines come out
+    /// smooth but slightly soft and their apparent weight varies a little with the angle.
+    #[default]
+    Analytic,
+    /// Geometry is drawn aliased and smoothed only by the downsampling of a supersampled
+    /// surface, like MSAA. Thin diagonal lines keep a more even weight and crisper look but
+    /// show faint stair steps, with `factor² + 1` coverage levels per pixel. Text keeps its
+    /// own anti-aliasing.
+    Supersampled,
+}
+
+/// How coordinates computed as floats by the backend's own helpers are snapped.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round to the nearest pixel edge
+    #[default]
+    Round,
+    /// Round down to the pixel edge
+    Floor,
+    /// Snap to the center of the pixel, which keeps odd-width strokes crisp
+    PixelCenter,
+}
+
+impl RoundingMode {
+    fn apply(self, v: f32) -> f32 {
+        match self {
+            RoundingMode::Round => v.round(),
+            RoundingMode::Floor => v.floor(),
+            RoundingMode::PixelCenter => v.floor() + 0.5,
+        }
+    }
+
+    fn point(self, (x, y): (f32, f32)) -> (f32, f32) {
+        (self.apply(x), self.apply(y))
+    }
+}
+
+// `Color` isn't hashable so its channels serve as key
+struct ColorGroupedPaths(HashMap<(u8, u8, u8, u8), Path>);
+
+impl ColorGroupedPaths {
+    fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    fn get(&mut self, color: Color) -> &mut Path {
+        let key = (color.a(), color.r(), color.g(), color.b());
+
+        self.0.entry(key).or_default()
+    }
+}
+
+/// Shape of markers drawn by [`SkiaBackend::draw_marker_shadowed`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MarkerShape {
+    Circle,
+    Square,
+    Triangle,
+    Diamond,
+}
+
+impl MarkerShape {
+    // Adds the shape with its bounding box of side `size` centered on `(cx, cy)`
+    fn add_to(self, path: &mut Path, (cx, cy): (f32, f32), size: f32) {
+        let half = size / 2.0;
+
+        match self {
+            MarkerShape::Circle => {
+                path.add_circle((cx, cy), half, None);
+            }
+            MarkerShape::Square => {
+                path.add_rect(Rect::from_xywh(cx - half, cy - half, size, size), None);
+            }
+            MarkerShape::Triangle => {
+                path.move_to((cx, cy - half));
+                path.line_to((cx + half, cy + half));
+                path.line_to((cx - half, cy + half));
+                path.close();
+            }
+            MarkerShape::Diamond => {
+                path.move_to((cx, cy - half));
+                path.line_to((cx + half, cy));
+                path.line_to((cx, cy + half));
+                path.line_to((cx - half, cy));
+                path.close();
+            }
+        }
+    }
+}
+
+/// Direction of the axis an element like [`SkiaBackend::draw_axis_break`] belongs to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug)]
+pub enum SkiaError {
+    Typeface,
+    ImageFromRaster,
+    Surface,
+    Encode,
+    Io(io::Error),
+    BitmapBufferTooSmall { expected: usize, got: usize },
+    Deserialize,
+    SvgPath,
+}
+
+impl Display for SkiaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Debug::fmt(self, f)
+    }
+}
+
+impl StdError for SkiaError {}
+
+impl<'a> SkiaBackend<'a> {
+    pub fn new(canvas: &'a mut Canvas, w: u32, h: u32) -> Self {
+        Self::with_target(Target::Canvas(canvas), w, h)
+    }
+
+    /// Draw onto the canvas of `surface`, which also determines the size.
+    ///
+    /// Unlike with [`Self::new`], [`DrawingBackend::present`] then flushes and submits the
+    /// surface's pending GPU work, so nothing needs to be flushed externally. Raster
+    /// surfaces need no flushing.
+    pub fn from_surface(surface: &'a mut Surface) -> Self {
+        let (w, h) = (surface.width() as u32, surface.height() as u32);
+
+        Self::with_target(Target::Surface(surface), w, h)
+    }
+
+    fn with_target(target: Target<'a>, w: u32, h: u32) -> Self {
+        Self {
+            target,
+            width: w,
+            height: h,
+            style: StyleState::default(),
+            saved_styles: Vec::new(),
+            reset_on_present: false,
+            dither: false,
+            rounding: RoundingMode::default(),
+            line_gamma: 1.0,
+            premul_gradients: false,
+            bitmap_alpha_type: AlphaType::Unpremul,
+            bitmap_color_type: ColorType::RGBA8888,
+            clip_depth: 0,
+            plot_area: None,
+            plot_clip_level: None,
+            anti_alias: true,
+            aa_method: AaMethod::Analytic,
+            typefaces: Vec::new(),
+            shadow_color: BackendColor {
+                alpha: 0.4,
+                rgb: (0, 0, 0),
+            },
+            pixel_paint: None,
+            line_clip_bounds: None,
+            tee: None,
+            #[cfg(feature = "shaping")]
+            font_collection: None,
+            #[cfg(feature = "shaping")]
+            tabular_figures: false,
+        }
+    }
+
+    /// Draw onto a `w` x `h` device pixel canvas at `scale` device pixels per logical pixel,
+    /// e.g. `2.0` for HiDPI displays.
+    ///
+    /// [`DrawingBackend::get_size`] reports the logical size and the canvas is scaled so
+    /// that coordinates, stroke widths and font sizes are all magnified. The scale stays
+    /// applied to `canvas` after the backend is dropped.
+    pub fn with_scale(canvas: &'a mut Canvas, w: u32, h: u32, scale: f32) -> Self {
+        let scale = if scale > 0.0 { scale } else { 1.0 };
+        canvas.scale((scale, scale));
+
+        let w = (w as f32 / scale).round() as u32;
+        let h = (h as f32 / scale).round() as u32;
+
+        Self::new(canvas, w, h)
+    }
+
+    /// Forward every draw to the canvas of `recorder` in addition to `canvas`.
+    ///
+    /// Recording starts right away over the `w` x `h` bounds; [`Self::finish_tee`]
+    /// returns the recorded picture. Every draw call is issued on both canvases, so the
+    /// drawing overhead roughly doubles, but the chart itself only has to be built once.
+    pub fn new_tee(
+        canvas: &'a mut Canvas,
+        recorder: &'a mut PictureRecorder,
+        w: u32,
+        h: u32,
+    ) -> Self {
+        recorder.begin_recording(Rect::from_wh(w as f32, h as f32), None);
+
+        Self {
+            tee: Some(recorder),
+            ..Self::new(canvas, w, h)
+        }
+    }
+
+    /// Stop recording and return the picture of everything drawn since [`Self::new_tee`].
+    ///
+    /// Returns `None` if the backend was not created in tee mode or was already finished.
+    pub fn finish_tee(&mut self) -> Option<Picture> {
+        self.tee.take()?.finish_recording_as_picture(None)
+    }
+
+    /// The live canvas. Draws issued on it directly are not recorded in tee mode.
+    pub fn canvas_mut(&mut self) -> &mut Canvas {
+        self.target.canvas()
+    }
+
+    /// The live canvas's current local-to-device transform, e.g. to map device coordinates
+    /// back to logical ones for hit-testing by inverting it.
+    ///
+    /// This includes every transform on the canvas, such as the HiDPI scale of
+    /// [`Self::with_scale`], the one applied by
+    /// [`SkiaSurfaceBackend::new_supersampled`](crate::SkiaSurfaceBackend::new_supersampled)
+    /// or translations set up by the caller.
+    ///
+    /// Returns `None` for backends created with [`Self::from_surface`] since reaching a
+    /// surface's canvas takes a mutable borrow; [`Self::canvas_mut`] still gives access to
+    /// their matrix.
+    pub fn current_matrix(&self) -> Option<Matrix> {
+        self.target
+            .canvas_ref()
+            .map(|canvas| canvas.local_to_device_as_3x3())
+    }
+
+    // The live canvas followed by the recording canvas in tee mode
+    pub(crate) fn canvases(&mut self) -> impl Iterator<Item = &mut Canvas> {
+        let recording = self
+            .tee
+            .as_deref_mut()
+            .and_then(PictureRecorder::recording_canvas);
+
+        std::iter::once(self.target.canvas()).chain(recording)
+    }
+
+    pub fn set_blend_mode(&mut self, blend_mode: Option<BlendMode>) -> &mut Self {
+        self.style.blend_mode = blend_mode;
+
+        self
+    }
+
+    /// Dash strokes with alternating on and off `intervals` in pixels, starting `phase`
+    /// pixels into the pattern. Fills are never dashed.
+    pub fn set_dash(&mut self, intervals: &[f32], phase: f32) -> &mut Self {
+        self.style.dash = Some(Dash {
+            intervals: intervals.to_vec(),
+            phase,
+            relative: false,
+        });
+
+        self
+    }
+
+    /// Dash strokes with intervals given in multiples of the stroke width.
+    ///
+    /// The intervals and phase are scaled by the width of each stroke when it is drawn,
+    /// so lines of different weights keep the same dash proportions.
+    pub fn set_dash_relative(&mut self, intervals_in_widths: &[f32], phase: f32) -> &mut Self {
+        self.style.dash = Some(Dash {
+            intervals: intervals_in_widths.to_vec(),
+            phase,
+            relative: true,
+        });
+
+        self
+    }
+
+    /// Dash strokes with absolute intervals and cap every dash segment with `cap`.
+    ///
+    /// Round and square caps extend each dash by half the stroke width on both ends, so
+    /// `[0.0, gap]` with [`PaintCap::Round`] draws dots.
+    pub fn set_dash_with_cap(&mut self, intervals: &[f32], phase: f32, cap: PaintCap) -> &mut Self {
+        self.style.dash = Some(Dash {
+            intervals: intervals.to_vec(),
+            phase,
+            relative: false,
+        });
+
+        self.set_line_cap(Some(cap))
+    }
+
+    /// Cap used for the ends of strokes and of each dash segment, `None` for Skia's default.
+    pub fn set_line_cap(&mut self, cap: Option<PaintCap>) -> &mut Self {
+        self.style.line_cap = cap;
+
+        self
+    }
+
+    /// Join used where stroke segments meet, `None` for Skia's default.
+    pub fn set_line_join(&mut self, join: Option<PaintJoin>) -> &mut Self {
+        self.style.line_join = join;
+
+        self
+    }
+
+    pub fn clear_dash(&mut self) -> &mut Self {
+        self.style.dash = None;
+
+        self
+    }
+
+    /// Push the transient style onto a stack to bring it back with [`Self::restore_style`].
+    ///
+    /// The transient style is what [`Self::set_blend_mode`], the dash setters,
+    /// [`Self::set_dash_scale`], [`Self::set_line_cap`], [`Self::set_line_join`] and
+    /// [`Self::set_linear_gradient`] configure. Rendering options such as anti-aliasing,
+    /// dithering and line gamma aren't part of it.
+    pub fn save_style(&mut self) -> &mut Self {
+        self.saved_styles.push(self.style.clone());
+
+        self
+    }
+
+    /// Pop the transient style last pushed by [`Self::save_style`], or reset it if there is
+    /// none.
+    pub fn restore_style(&mut self) -> &mut Self {
+        self.style = self.saved_styles.pop().unwrap_or_default();
+
+        self
+    }
+
+    /// Run `f` and afterwards restore the transient style, see [`Self::save_style`], to how it
+    /// was before, even if `f` panics.
+    ///
+    /// Scopes a blend mode or gradient to a block of draws without leaking it into other
+    /// drawing areas sharing the backend.
+    pub fn with_style_scope<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        struct Restore<'b, 'a> {
+            backend: &'b mut SkiaBackend<'a>,
+            style: StyleState,
+        }
+
+        impl Drop for Restore<'_, '_> {
+            fn drop(&mut self) {
+                self.backend.style = std::mem::take(&mut self.style);
+            }
+        }
+
+        let style = self.style.clone();
+        let guard = Restore {
+            backend: self,
+            style,
+        };
+
+        f(guard.backend)
+    }
+
+    /// Reset transient style such as blend mode and dash pattern to their defaults.
+    pub fn reset_style(&mut self) -> &mut Self {
+        self.style = StyleState::default();
+
+        self
+    }
+
+    /// Whether [`DrawingBackend::present`] calls [`Self::reset_style`].
+    ///
+    /// Disabled by default so style carries over between frames. The reset happens after
+    /// any flushing `present` does, so it never affects draws issued before the call.
+    pub fn set_reset_on_present(&mut self, enabled: bool) -> &mut Self {
+        self.reset_on_present = enabled;
+
+        self
+    }
+
+    /// Anti-alias geometry and clips. Enabled by default.
+    ///
+    /// Disabling it keeps edges sharp, e.g. for pixel-art plots or byte-for-byte comparable
+    /// output. Text is always anti-aliased and [`DrawingBackend::draw_pixel`] never is.
+    pub fn set_anti_alias(&mut self, enabled: bool) -> &mut Self {
+        self.anti_alias = enabled;
+
+        self
+    }
+
+    /// How geometry and clips are anti-aliased. Defaults to [`AaMethod::Analytic`].
+    ///
+    /// [`AaMethod::Supersampled`] only looks right on a surface created with
+    /// [`SkiaSurfaceBackend::new_supersampled`](crate::SkiaSurfaceBackend::new_supersampled),
+    /// elsewhere edges are left aliased.
+    pub fn set_aa_method(&mut self, method: AaMethod) -> &mut Self {
+        self.aa_method = method;
+
+        self
+    }
+
+    fn analytic_aa(&self) -> bool {
+        self.anti_alias && self.aa_method == AaMethod::Analytic
+    }
+
+    /// Dither gradient fills to avoid visible banding on 8-bit targets. Disabled by default.
+    ///
+    /// Solid colors are unaffected, Skia only dithers shaded paints.
+    pub fn set_dither(&mut self, enabled: bool) -> &mut Self {
+        self.dither = enabled;
+
+        self
+    }
+
+    /// Snapping applied to float coordinates computed by [`Self::draw_sparkline`],
+    /// [`Self::draw_ticks`], [`Self::draw_boxplot`] and [`Self::draw_waterfall_connector`].
+    /// Defaults to [`RoundingMode::Round`].
+    ///
+    /// Helpers drawing slanted or curved geometry, such as [`Self::draw_arrow`],
+    /// [`Self::draw_axis_break`], [`Self::draw_radar`] and
+    /// [`Self::draw_variable_width_path`], keep their exact coordinates since snapping would
+    /// distort their shapes.
+    ///
+    /// Integer [`BackendCoord`]s from plotters lie on pixel edges, so a 1px stroke between
+    /// them covers two half pixels; [`RoundingMode::PixelCenter`] avoids that blur for the
+    /// helper geometry at the cost of shifting it by half a pixel.
+    pub fn set_rounding(&mut self, mode: RoundingMode) -> &mut Self {
+        self.rounding = mode;
+
+        self
+    }
+
+    /// Boost the intensity of strokes thinner than 2px, which otherwise look faint because
+    /// anti-aliasing spreads them over partially covered pixels. Defaults to `1.0`, i.e. no
+    /// adjustment.
+    ///
+    /// The visible intensity `alpha * coverage` is raised to `1 / gamma`, first by raising
+    /// the stroke's alpha and, once that is opaque, by widening it. This is an empirical
+    /// correction; values around `1.5` to `2.2` work well for dark lines on light backgrounds.
+    pub fn set_line_gamma(&mut self, gamma: f32) -> &mut Self {
+        self.line_gamma = gamma;
+
+        self
+    }
+
+    /// Multiply the intervals and phase of every dash pattern by `scale`, e.g. to make dashes
+    /// coarser for print output without changing the patterns. Defaults to `1.0`.
+    pub fn set_dash_scale(&mut self, scale: f32) -> &mut Self {
+        self.style.dash_scale = scale;
+
+        self
+    }
+
+    /// Interpolate the colors of gradients drawn by helpers such as [`Self::apply_vignette`]
+    /// and fills set with [`Self::set_linear_gradient`] in premultiplied space. Disabled by
+    /// default.
+    ///
+    /// Straight alpha interpolation blends towards the color channels of a transparent
+    /// stop, so a fade from a color to transparent black passes through murky gray.
+    pub fn set_premultiplied_gradients(&mut self, enabled: bool) -> &mut Self {
+        self.premul_gradients = enabled;
+
+        self
+    }
+
+    /// Fill the rectangles, circles and polygons drawn through [`DrawingBackend`] with a
+    /// linear gradient from `start` to `end` instead of their style's color, until
+    /// [`Self::clear_shader`] is called. Strokes and lines are unaffected.
+    ///
+    /// `stops` are positions from `0.0` to `1.0` along the gradient with their color. The
+    /// alpha of the fill style still applies on top of the gradient.
+    pub fn set_linear_gradient(
+        &mut self,
+        start: BackendCoord,
+        end: BackendCoord,
+        stops: &[(f32, BackendColor)],
+    ) -> &mut Self {
+        let (positions, colors): (Vec<_>, Vec<_>) = stops
+            .iter()
+            .map(|&(pos, color)| (pos, skia_color(color)))
+            .unzip();
+
+        self.style.fill_gradient = Some(LinearGradient {
+            points: (start.into(), end.into()),
+            positions,
+            colors,
+        });
+
+        self
+    }
+
+    /// Go back to filling shapes with their style's color after [`Self::set_linear_gradient`].
+    pub fn clear_shader(&mut self) -> &mut Self {
+        self.style.fill_gradient = None;
+
+        self
+    }
+
+    /// How the alpha channel of buffers passed to [`DrawingBackend::blit_bitmap`] and the
+    /// other `blit_bitmap_*` methods is interpreted. Defaults to [`AlphaType::Unpremul`],
+    /// i.e. straight alpha.
+    ///
+    /// Use [`AlphaType::Premul`] for premultiplied buffers, or [`AlphaType::Opaque`] to
+    /// ignore the alpha channel.
+    pub fn set_bitmap_alpha_type(&mut self, alpha_type: AlphaType) -> &mut Self {
+        self.bitmap_alpha_type = alpha_type;
+
+        self
+    }
+
+    /// Pixel format of buffers passed to [`DrawingBackend::blit_bitmap`] and
+    /// [`Self::blit_bitmap_rounded`], whose rows are expected to be tightly packed.
+    /// Defaults to [`ColorType::RGBA8888`].
+    ///
+    /// Use [`Self::blit_bitmap_with_format`] for buffers with padded rows.
+    pub fn set_bitmap_color_type(&mut self, color_type: ColorType) -> &mut Self {
+        self.bitmap_color_type = color_type;
+
+        self
+    }
+
+    fn gradient_flags(&self) -> GradientFlags {
+        if self.premul_gradients {
+            GradientFlags::INTERPOLATE_COLORS_IN_PREMUL
+        } else {
+            GradientFlags::empty()
+        }
+    }
+
+    // Returns the adjusted alpha and stroke width
+    fn line_gamma_adjust(&self, alpha: f32, stroke_width: f32) -> (f32, f32) {
+        // Hairlines are drawn one pixel wide
+        let width = if stroke_width == 0.0 {
+            1.0
+        } else {
+            stroke_width
+        };
+
+        if self.line_gamma == 1.0 || self.line_gamma <= 0.0 || width >= 2.0 || alpha == 0.0 {
+            return (alpha, stroke_width);
+        }
+
+        // A line on integer coordinates straddles two pixels, each covered by half its width
+        let intensity = alpha * width / 2.0;
+        let boost = intensity.powf(1.0 / self.line_gamma) / intensity;
+
+        let new_alpha = (alpha * boost).min(1.0);
+        let new_width = (width * alpha * boost / new_alpha).min(2.0);
+
+        (new_alpha, new_width)
+    }
+
+    fn paint(&self, color: BackendColor) -> Paint {
+        let mut paint = Paint::default();
+        paint
+            .set_color(skia_color(color))
+            .set_dither(self.dither)
+            .set_anti_alias(self.analytic_aa());
+
+        if let Some(mode) = self.style.blend_mode {
+            paint.set_blend_mode(mode);
+        }
+
+        paint
+    }
+
+    fn style_paint<S: BackendStyle>(&self, style: &S, fill: bool) -> Paint {
+        let mut paint = self.paint(style.color());
+        let stroke_width = style.stroke_width() as f32;
+
+        paint.set_stroke_width(stroke_width);
+
+        if fill {
+            paint.set_style(PaintStyle::Fill);
+        } else {
+            let (alpha, width) = self.line_gamma_adjust(paint.alpha_f(), stroke_width);
+            paint
+                .set_style(PaintStyle::Stroke)
+                .set_alpha_f(alpha)
+                .set_stroke_width(width);
+
+            if let Some(cap) = self.style.line_cap {
+                paint.set_stroke_cap(cap);
+            }
+
+            if let Some(join) = self.style.line_join {
+                paint.set_stroke_join(join);
+            }
+
+            if let Some(ref dash) = self.style.dash {
+                paint.set_path_effect(dash.path_effect(stroke_width, self.style.dash_scale));
+            }
+        }
+
+        paint
+    }
+
+    // Pixels are never anti-aliased since that would smear them over their neighbours
+    fn pixel_paint(mut paint: Paint) -> Paint {
+        paint.set_anti_alias(false);
+
+        paint
+    }
+
+    // Like `style_paint` but fills with the gradient of `set_linear_gradient` if there is one
+    fn shape_paint<S: BackendStyle>(&self, style: &S, fill: bool) -> Paint {
+        let mut paint = self.style_paint(style, fill);
+
+        if let (true, Some(gradient)) = (fill, &self.style.fill_gradient) {
+            paint.set_shader(gradient.shader(self.gradient_flags()));
+        }
+
+        paint
+    }
+
+    // Text is always anti-aliased, independent of how geometry is rendered
+    fn text_paint(&self, color: BackendColor) -> Paint {
+        let mut paint = self.paint(color);
+        paint.set_anti_alias(true);
+
+        paint
+    }
+
+    /// Load a font from the bytes of a font file, e.g. a bundled `.ttf`, and prefer it over
+    /// system fonts whenever a text style asks for its family.
+    ///
+    /// Registering several styles of one family picks the one matching the text style, or
+    /// the first registered one if none matches.
+    pub fn register_typeface(&mut self, data: &[u8]) -> Result<&mut Self, SkiaError> {
+        let typeface = FontMgr::new()
+            .new_from_data(data, None)
+            .ok_or(SkiaError::Typeface)?;
+
+        self.typefaces.push(typeface);
+
+        Ok(self)
+    }
+
+    fn typeface<TStyle: BackendTextStyle>(&self, font: &TStyle) -> Option<Typeface> {
+        let family = font.family();
+        let style = font_style(font.style());
+
+        let mut registered = self
+            .typefaces
+            .iter()
+            .filter(|typeface| typeface.family_name().eq_ignore_ascii_case(family.as_str()));
+
+        let first = registered.clone().next();
+
+        registered
+            .find(|typeface| typeface.font_style() == style)
+            .or(first)
+            .cloned()
+            .or_else(|| Typeface::new(family.as_str(), style))
+    }
+
+    fn font<TStyle: BackendTextStyle>(&self, font: &TStyle) -> Result<Font, SkiaError> {
+        let typeface = self.typeface(font).ok_or(SkiaError::Typeface)?;
+
+        let mut font = Font::new(typeface, Some(font.size() as f32 * FONT_EM_FACTOR));
+        font.set_edging(Edging::AntiAlias);
+
+        Ok(font)
+    }
+
+    /// Shape text through `collection` instead of resolving a single typeface.
+    ///
+    /// Once set, [`Self::draw_text_angled`] lays text out as a paragraph so font fallback
+    /// and OpenType features configured on the collection apply. Requires the `shaping`
+    /// feature.
+    #[cfg(feature = "shaping")]
+    pub fn set_font_collection(&mut self, collection: FontCollection) -> &mut Self {
+        self.font_collection = Some(collection);
+
+        self
+    }
+
+    /// Enable the `tnum` OpenType feature for text shaped through the font collection of
+    /// [`Self::set_font_collection`], so digits get equal widths and numbers line up in
+    /// columns. Disabled by default.
+    ///
+    /// Requires the `shaping` feature. Fonts without tabular figures keep their default
+    /// digits.
+    #[cfg(feature = "shaping")]
+    pub fn set_tabular_figures(&mut self, enabled: bool) -> &mut Self {
+        self.tabular_figures = enabled;
+
+        self
+    }
+
+    // Width and line height of `text` at `font_size`, shaped if there's a font collection
+    fn text_extent<TStyle: BackendTextStyle>(
+        &self,
+        text: &str,
+        style: &TStyle,
+        font_size: f32,
+    ) -> Result<(f32, f32), SkiaError> {
+        #[cfg(feature = "shaping")]
+        if let Some(ref collection) = self.font_collection {
+            let paragraph = self.paragraph(collection, text, style, font_size, style.color());
+
+            return Ok((paragraph.max_intrinsic_width(), paragraph.height()));
+        }
+
+        let paint = self.text_paint(style.color());
+        let mut font = self.font(style)?;
+        font.set_size(font_size);
+
+        // The line's full extent rather than the ink box since labels are laid out using it
+        let (width, _) = font.measure_str(text, Some(&paint));
+        let (_, metrics) = font.metrics();
+
+        Ok((width, metrics.descent - metrics.ascent))
+    }
+
+    #[cfg(feature = "shaping")]
+    fn paragraph<TStyle: BackendTextStyle>(
+        &self,
+        collection: &FontCollection,
+        text: &str,
+        style: &TStyle,
+        font_size: f32,
+        color: BackendColor,
+    ) -> Paragraph {
+        let mut text_style = TextStyle::new();
+        text_style
+            .set_font_families(&[style.family().as_str()])
+            .set_font_size(font_size)
+            .set_font_style(font_style(style.style()))
+            .set_foreground_paint(&self.text_paint(color));
+
+        if self.tabular_figures {
+            text_style.add_font_feature("tnum", 1);
+        }
+
+        let mut paragraph_style = ParagraphStyle::new();
+        paragraph_style.set_text_style(&text_style);
+
+        let mut builder = ParagraphBuilder::new(&paragraph_style, collection.clone());
+        builder.add_text(text);
+
+        let mut paragraph = builder.build();
+        paragraph.layout(f32::INFINITY);
+
+        paragraph
+    }
+
+    // Draws unrotated text anchored at `pos`, in `color` rather than the style's
+    fn draw_str_<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        color: BackendColor,
+        pos: BackendCoord,
+    ) -> Result<(), DrawingErrorKind<SkiaError>> {
+        #[cfg(feature = "shaping")]
+        if let Some(ref collection) = self.font_collection {
+            let font_size = style.size() as f32 * FONT_EM_FACTOR;
+            let paragraph = self.paragraph(collection, text, style, font_size, color);
+            let size = (paragraph.max_intrinsic_width(), paragraph.height());
+            let (dx, dy) = Self::anchor_offset(style.anchor(), size);
+
+            let origin = (pos.0 as f32 + dx, pos.1 as f32 + dy);
+
+            for canvas in self.canvases() {
+                paragraph.paint(canvas, origin);
+            }
+
+            return Ok(());
+        }
+
+        let paint = self.text_paint(color);
+        let font = self.font(style).map_err(DrawingErrorKind::DrawingError)?;
+        let origin = Self::text_origin(&font, &paint, text, style.anchor(), pos);
+
+        for canvas in self.canvases() {
+            canvas.draw_str(text, origin, &font, &paint);
+        }
+
+        Ok(())
+    }
+
+    // Offset from the anchor point to the top left of a `width` x `height` line box
+    fn anchor_offset(anchor: Pos, (width, height): (f32, f32)) -> (f32, f32) {
+        let dx = match anchor.h_pos {
+            HPos::Left => 0.0,
+            HPos::Right => -width,
+            HPos::Center => -width / 2.0,
+        };
+
+        let dy = match anchor.v_pos {
+            VPos::Top => 0.0,
+            VPos::Center => -height / 2.0,
+            VPos::Bottom => -height,
+        };
+
+        (dx, dy)
+    }
+
+    fn text_origin(
+        font: &Font,
+        paint: &Paint,
+        text: &str,
+        anchor: Pos,
+        pos: BackendCoord,
+    ) -> (f32, f32) {
+        let (width, _) = font.measure_str(text, Some(paint));
+
+        let dx = match anchor.h_pos {
+            HPos::Left => 0.0,
+            HPos::Right => -width,
+            HPos::Center => -width / 2.0,
+        };
+
+        // The font's metrics rather than each string's ink keep labels on one baseline
+        let (_, metrics) = font.metrics();
+
+        let dy = match anchor.v_pos {
+            VPos::Top => -metrics.ascent,
+            VPos::Center => -(metrics.ascent + metrics.descent) / 2.0,
+            VPos::Bottom => -metrics.descent,
+        };
+
+        (pos.0 as f32 + dx, pos.1 as f32 + dy)
+    }
+
+    /// Draw text rotated clockwise by `angle_deg` degrees around its anchor point `pos`.
+    pub fn draw_text_angled<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: BackendCoord,
+        angle_deg: f32,
+    ) -> Result<(), DrawingErrorKind<SkiaError>> {
+        for canvas in self.canvases() {
+            canvas.save();
+            canvas.rotate(angle_deg, Some(pos.into()));
+        }
+
+        let res = self.draw_str_(text, style, style.color(), pos);
+
+        for canvas in self.canvases() {
+            canvas.restore();
+        }
+
+        res
+    }
+
+    fn polyline<I: IntoIterator<Item = BackendCoord>>(points: I) -> Path {
+        let mut points = points.into_iter();
+        let mut path = Path::new();
+
+        if let Some(point) = points.next() {
+            path.move_to(point);
+
+            for point in points {
+                path.line_to(point);
+            }
+        }
+
+        path
+    }
+
+    fn draw_path_<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+        filled: bool,
+    ) {
+        let paint = self.shape_paint(style, filled);
+
+        let (path, canvas_clip) = match self.line_clip_bounds {
+            // Splitting dashed strokes would restart their pattern at every cut
+            Some(bounds) if !filled && self.style.dash.is_none() => {
+                (Self::clipped_polyline(bounds, path), None)
+            }
+            bounds => (Self::polyline(path), bounds),
+        };
+
+        for canvas in self.canvases() {
+            if let Some(bounds) = canvas_clip {
+                canvas.save();
+                canvas.clip_rect(bounds, ClipOp::Intersect, false);
+            }
+
+            canvas.draw_path(&path, &paint);
+
+            if canvas_clip.is_some() {
+                canvas.restore();
+            }
+        }
+    }
+
+    /// Cut the geometry of lines and stroked paths drawn through [`DrawingBackend`] to the
+    /// rectangle between the given corners before handing it to Skia, or stop doing so for
+    /// `None`.
+    ///
+    /// Saves Skia from processing far off-screen coordinates in heavily zoomed plots.
+    /// Dashed strokes and filled shapes can't be cut without changing their look, so those
+    /// get clipped by the canvas instead.
+    pub fn set_line_clip_bounds(
+        &mut self,
+        bounds: Option<(BackendCoord, BackendCoord)>,
+    ) -> &mut Self {
+        self.line_clip_bounds = bounds.map(|(upper_left, bottom_right)| {
+            Rect::new(
+                upper_left.0 as f32,
+                upper_left.1 as f32,
+                bottom_right.0 as f32,
+                bottom_right.1 as f32,
+            )
+            .sorted()
+        });
+
+        self
+    }
+
+    // Polyline of only the parts of each segment inside `bounds`
+    fn clipped_polyline<I: IntoIterator<Item = BackendCoord>>(bounds: Rect, points: I) -> Path {
+        let mut points = points.into_iter().map(|(x, y)| (x as f32, y as f32));
+        let mut path = Path::new();
+
+        let Some(mut prev) = points.next() else {
+            return path;
+        };
+
+        let mut pen = None;
+
+        for point in points {
+            if let Some((from, to)) = clip_segment(bounds, prev, point) {
+                if pen != Some(from) {
+                    path.move_to(from);
+                }
+
+                path.line_to(to);
+                pen = Some(to);
+            }
+
+            prev = point;
+        }
+
+        path
+    }
+
+    /// Fill a polygon with `fill_style` and stroke its closed outline with `stroke_style`.
+    pub fn draw_polygon<F, S, I>(&mut self, vert: I, fill_style: &F, stroke_style: &S)
+    where
+        F: BackendStyle,
+        S: BackendStyle,
+        I: IntoIterator<Item = BackendCoord>,
+    {
+        let mut path = Self::polyline(vert);
+        path.close();
+
+        let fill = self.style_paint(fill_style, true);
+        let stroke = self.style_paint(stroke_style, false);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &fill);
+            canvas.draw_path(&path, &stroke);
+        }
+    }
+
+    fn annular_wedge_path(
+        (cx, cy): (f32, f32),
+        inner_r: f32,
+        outer_r: f32,
+        start_angle: f32,
+        sweep: f32,
+    ) -> Path {
+        let outer = Rect::new(cx - outer_r, cy - outer_r, cx + outer_r, cy + outer_r);
+        let inner = Rect::new(cx - inner_r, cy - inner_r, cx + inner_r, cy + inner_r);
+        let mut path = Path::new();
+
+        if sweep.abs() >= 360.0 - 1e-3 {
+            // Two concentric circles in opposite directions so there are no radial edges
+            path.add_circle((cx, cy), outer_r, PathDirection::CW);
+
+            if inner_r > 0.0 {
+                path.add_circle((cx, cy), inner_r, PathDirection::CCW);
+            }
+        } else {
+            path.arc_to(outer, start_angle, sweep, true);
+
+            if inner_r > 0.0 {
+                path.arc_to(inner, start_angle + sweep, -sweep, false);
+            } else {
+                path.line_to((cx, cy));
+            }
+
+            path.close();
+        }
+
+        path
+    }
+
+    /// Draw a ring segment between `inner_r` and `outer_r`.
+    ///
+    /// Angles are in degrees, clockwise from the positive x-axis. A sweep of 360° draws
+    /// a full ring without a seam.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_annular_wedge<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        inner_r: f32,
+        outer_r: f32,
+        start_angle: f32,
+        sweep: f32,
+        style: &S,
+        fill: bool,
+    ) {
+        let center = (center.0 as f32, center.1 as f32);
+        let path = Self::annular_wedge_path(center, inner_r, outer_r, start_angle, sweep);
+        let paint = self.style_paint(style, fill);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &paint);
+        }
+    }
+
+    /// Stroke `values` as a polyline scaled to fill `rect`, optionally marking the minimum
+    /// and maximum points.
+    pub fn draw_sparkline<S: BackendStyle>(
+        &mut self,
+        rect: Rect,
+        values: &[f32],
+        style: &S,
+        mark_extremes: bool,
+    ) {
+        if values.is_empty() {
+            return;
+        }
+
+        let (min_idx, max_idx) = values.iter().enumerate().fold((0, 0), |(lo, hi), (i, v)| {
+            let lo = if *v < values[lo] { i } else { lo };
+            let hi = if *v > values[hi] { i } else { hi };
+
+            (lo, hi)
+        });
+
+        let (min, max) = (values[min_idx], values[max_idx]);
+        let step = rect.width() / (values.len() - 1).max(1) as f32;
+        let rounding = self.rounding;
+
+        let point = |i: usize| {
+            let x = rect.left + i as f32 * step;
+            let y = if max > min {
+                rect.bottom - (values[i] - min) / (max - min) * rect.height()
+            } else {
+                rect.center_y()
+            };
+
+            rounding.point((x, y))
+        };
+
+        let mut path = Path::new();
+        path.move_to(point(0));
+
+        for i in 1..values.len() {
+            path.line_to(point(i));
+        }
+
+        let mut paint = self.style_paint(style, false);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &paint);
+        }
+
+        if mark_extremes {
+            let radius = paint.stroke_width().max(1.0) * 1.5;
+            paint.set_style(PaintStyle::Fill).set_path_effect(None);
+
+            for canvas in self.canvases() {
+                canvas.draw_circle(point(min_idx), radius, &paint);
+                canvas.draw_circle(point(max_idx), radius, &paint);
+            }
+        }
+    }
+
+    /// Tight bounds of the polyline through `points`, inflated by half of `stroke_width`.
+    pub fn path_bounds(points: &[BackendCoord], stroke_width: f32) -> Rect {
+        let path = Self::polyline(points.iter().copied());
+        let half = stroke_width / 2.0;
+
+        path.compute_tight_bounds().with_outset((half, half))
+    }
+
+    /// Draw labels in order, nudging each one up or down by its line height when it
+    /// collides with an already placed label and skipping it if that doesn't help.
+    ///
+    /// Returns whether each label was placed.
+    pub fn draw_labels_no_overlap<TStyle: BackendTextStyle>(
+        &mut self,
+        labels: &[(BackendCoord, String)],
+        style: &TStyle,
+    ) -> Result<Vec<bool>, DrawingErrorKind<SkiaError>> {
+        let font_size = style.size() as f32 * FONT_EM_FACTOR;
+
+        let mut placed: Vec<Rect> = Vec::with_capacity(labels.len());
+        let mut result = Vec::with_capacity(labels.len());
+
+        for (pos, text) in labels {
+            let size = self
+                .text_extent(text, style, font_size)
+                .map_err(DrawingErrorKind::DrawingError)?;
+
+            let (dx, dy) = Self::anchor_offset(style.anchor(), size);
+            let bounds = Rect::from_xywh(pos.0 as f32 + dx, pos.1 as f32 + dy, size.0, size.1);
+
+            let free = [0.0, -size.1, size.1].into_iter().find(|&dy| {
+                let candidate = bounds.with_offset((0.0, dy));
+
+                !placed.iter().any(|other| other.intersects(candidate))
+            });
+
+            if let Some(dy) = free {
+                for canvas in self.canvases() {
+                    canvas.save();
+                    canvas.translate((0.0, dy));
+                }
+
+                let res = self.draw_str_(text, style, style.color(), *pos);
+
+                for canvas in self.canvases() {
+                    canvas.restore();
+                }
+
+                res?;
+                placed.push(bounds.with_offset((0.0, dy)));
+            }
+
+            result.push(free.is_some());
+        }
+
+        Ok(result)
+    }
+
+    /// Flush pending GPU work to the context without submitting it.
+    ///
+    /// Unlike [`DrawingBackend::present`] this doesn't finalize the frame, so style isn't
+    /// reset and the backend can keep drawing. Does nothing on raster canvases.
+    #[cfg(feature = "gpu")]
+    pub fn flush(&mut self) -> &mut Self {
+        if let Some(mut context) = self.target.canvas().direct_context() {
+            context.flush(None);
+        }
+
+        self
+    }
+
+    /// Draw the polyline through `points` with a caller-supplied paint.
+    ///
+    /// The `*_with_paint` methods use `paint` as is, so blend mode, dash and other backend
+    /// state are ignored.
+    pub fn draw_path_with_paint<I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        points: I,
+        paint: &Paint,
+        close: bool,
+    ) {
+        let mut path = Self::polyline(points);
+
+        if close {
+            path.close();
+        }
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, paint);
+        }
+    }
+
+    pub fn draw_rect_with_paint(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        paint: &Paint,
+    ) {
+        let rect = Rect::new(
+            upper_left.0 as f32,
+            upper_left.1 as f32,
+            bottom_right.0 as f32,
+            bottom_right.1 as f32,
+        );
+
+        for canvas in self.canvases() {
+            canvas.draw_rect(rect, paint);
+        }
+    }
+
+    pub fn draw_circle_with_paint(&mut self, center: BackendCoord, radius: f32, paint: &Paint) {
+        for canvas in self.canvases() {
+            canvas.draw_circle(center, radius, paint);
+        }
+    }
+
+    /// Draw tick marks perpendicular to the axis from `axis_start` to `axis_end` in a
+    /// single path.
+    ///
+    /// `positions` are fractions along the axis, 0 at the start and 1 at the end. Positive
+    /// `tick_length` draws ticks below a left-to-right axis (left of a top-to-bottom one),
+    /// negative lengths draw them on the other side.
+    pub fn draw_ticks<S: BackendStyle>(
+        &mut self,
+        axis_start: BackendCoord,
+        axis_end: BackendCoord,
+        positions: &[f32],
+        tick_length: f32,
+        style: &S,
+    ) {
+        let (x0, y0) = (axis_start.0 as f32, axis_start.1 as f32);
+        let (dx, dy) = (axis_end.0 as f32 - x0, axis_end.1 as f32 - y0);
+        let len = dx.hypot(dy);
+
+        if len == 0.0 {
+            return;
+        }
+
+        let (nx, ny) = (-dy / len * tick_length, dx / len * tick_length);
+        let mut path = Path::new();
+
+        for t in positions {
+            let (x, y) = (x0 + dx * t, y0 + dy * t);
+            path.move_to(self.rounding.point((x, y)));
+            path.line_to(self.rounding.point((x + nx, y + ny)));
+        }
+
+        let paint = self.style_paint(style, false);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &paint);
+        }
+    }
+
+    /// Replace the pixels of a rectangle with `color`, or make them transparent for `None`.
+    pub fn clear_rect(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        color: Option<BackendColor>,
+    ) {
+        let color = color.unwrap_or(BackendColor {
+            alpha: 0.0,
+            rgb: (0, 0, 0),
+        });
+
+        let mut paint = self.paint(color);
+        paint.set_blend_mode(BlendMode::Src);
+
+        self.draw_rect_with_paint(upper_left, bottom_right, &paint);
+    }
+
+    /// Draw a bubble whose area is proportional to `value`, radius `sqrt(value) * scale`.
+    ///
+    /// Scaling the area rather than the radius keeps large values from looking
+    /// quadratically bigger. Use [`Self::draw_bubble_linear`] for radius-proportional sizing.
+    ///
+    /// The bubble is filled with `fill_style` and then outlined with `border_style` if there
+    /// is one, e.g. `None::<&BackendColor>` for no border.
+    pub fn draw_bubble<F: BackendStyle, B: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        value: f32,
+        scale: f32,
+        fill_style: &F,
+        border_style: Option<&B>,
+    ) {
+        let radius = value.max(0.0).sqrt() * scale;
+        self.draw_bubble_(center, radius, fill_style, border_style);
+    }
+
+    /// Draw a bubble with radius `value * scale`.
+    ///
+    /// Like [`Self::draw_bubble`] it is filled with `fill_style` and outlined with
+    /// `border_style` if there is one.
+    pub fn draw_bubble_linear<F: BackendStyle, B: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        value: f32,
+        scale: f32,
+        fill_style: &F,
+        border_style: Option<&B>,
+    ) {
+        let radius = value.max(0.0) * scale;
+        self.draw_bubble_(center, radius, fill_style, border_style);
+    }
+
+    fn draw_bubble_<F: BackendStyle, B: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: f32,
+        fill_style: &F,
+        border_style: Option<&B>,
+    ) {
+        let fill = self.style_paint(fill_style, true);
+        let border = border_style.map(|style| self.style_paint(style, false));
+
+        for canvas in self.canvases() {
+            canvas.draw_circle(center, radius, &fill);
+
+            if let Some(ref border) = border {
+                canvas.draw_circle(center, radius, border);
+            }
+        }
+    }
+
+    /// Draw a circular track with a round-capped arc covering `progress` (0 to 1) of it,
+    /// starting at the top and running clockwise.
+    pub fn draw_progress_ring(
+        &mut self,
+        center: BackendCoord,
+        radius: f32,
+        thickness: f32,
+        progress: f32,
+        track_color: BackendColor,
+        fill_color: BackendColor,
+    ) {
+        let progress = progress.clamp(0.0, 1.0);
+
+        let mut paint = self.paint(track_color);
+        paint
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(thickness);
+
+        for canvas in self.canvases() {
+            canvas.draw_circle(center, radius, &paint);
+        }
+
+        if progress <= 0.0 {
+            return;
+        }
+
+        paint.set_color(skia_color(fill_color));
+
+        if progress >= 1.0 {
+            for canvas in self.canvases() {
+                canvas.draw_circle(center, radius, &paint);
+            }
+        } else {
+            let (cx, cy) = (center.0 as f32, center.1 as f32);
+            let oval = Rect::new(cx - radius, cy - radius, cx + radius, cy + radius);
+
+            paint.set_stroke_cap(PaintCap::Round);
+
+            for canvas in self.canvases() {
+                canvas.draw_arc(oval, -90.0, progress * 360.0, false, &paint);
+            }
+        }
+    }
+
+    /// Fill the area between the curve through `points` and the horizontal line at
+    /// `baseline_y`. Parts of the curve below the baseline are filled up to it as well.
+    pub fn fill_under_curve<S: BackendStyle>(
+        &mut self,
+        points: &[BackendCoord],
+        baseline_y: i32,
+        style: &S,
+    ) {
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return;
+        };
+
+        let mut path = Path::new();
+        path.move_to((first.0, baseline_y));
+
+        for point in points {
+            path.line_to(*point);
+        }
+
+        path.line_to((last.0, baseline_y));
+        path.close();
+
+        let paint = self.style_paint(style, true);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &paint);
+        }
+    }
+
+    /// Stroke a ring of width `ring_width` starting `inner_radius` away from `center`,
+    /// e.g. to highlight a hovered marker.
+    ///
+    /// With `glow` set, the ring additionally gets a soft halo of that blur sigma, drawn
+    /// in the same call.
+    pub fn draw_highlight_ring(
+        &mut self,
+        center: BackendCoord,
+        inner_radius: f32,
+        ring_width: f32,
+        color: BackendColor,
+        glow: Option<f32>,
+    ) {
+        let mut paint = self.paint(color);
+        paint
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(ring_width);
+
+        if let Some(sigma) = glow {
+            paint.set_mask_filter(MaskFilter::blur(BlurStyle::Solid, sigma, false));
+        }
+
+        for canvas in self.canvases() {
+            canvas.draw_circle(center, inner_radius + ring_width / 2.0, &paint);
+        }
+    }
+
+    // Checking the length up front keeps Skia from reading past the end of `src`
+    fn raster_image(
+        &self,
+        (iw, ih): (u32, u32),
+        src: &[u8],
+        color_type: ColorType,
+        row_bytes: Option<usize>,
+    ) -> Result<Image, SkiaError> {
+        let min_row_bytes = iw as usize * color_type.bytes_per_pixel();
+        let row_bytes = row_bytes.unwrap_or(min_row_bytes);
+
+        // The last row's padding doesn't need to be present
+        let expected = row_bytes * (ih as usize).saturating_sub(1) + min_row_bytes;
+
+        if src.len() < expected {
+            return Err(SkiaError::BitmapBufferTooSmall {
+                expected,
+                got: src.len(),
+            });
+        }
+
+        let info = ImageInfo::new(
+            (iw as i32, ih as i32),
+            color_type,
+            self.bitmap_alpha_type,
+            None,
+        );
+
+        // Copied since tee pictures and GPU canvases can read the pixels after the draw
+        // call returned
+        let data = Data::new_copy(src);
+
+        images::raster_from_data(&info, data, row_bytes).ok_or(SkiaError::ImageFromRaster)
+    }
+
+    /// Like [`DrawingBackend::blit_bitmap`] but for a buffer of pixels in `color_type`
+    /// whose rows start `row_bytes` apart, e.g. because they are padded.
+    ///
+    /// Skia has no packed 24-bit format; [`ColorType::RGB888x`] expects an unused fourth
+    /// byte per pixel.
+    pub fn blit_bitmap_with_format(
+        &mut self,
+        pos: BackendCoord,
+        (iw, ih): (u32, u32),
+        src: &[u8],
+        color_type: ColorType,
+        row_bytes: usize,
+    ) -> Result<(), DrawingErrorKind<SkiaError>> {
+        if iw == 0 || ih == 0 {
+            return Ok(());
+        }
+
+        let img = self
+            .raster_image((iw, ih), src, color_type, Some(row_bytes))
+            .map_err(DrawingErrorKind::DrawingError)?;
+
+        for canvas in self.canvases() {
+            canvas.draw_image(&img, pos, None);
+        }
+
+        Ok(())
+    }
+
+    /// Blit an image buffer clipped to a rectangle with corner radius `radius`.
+    pub fn blit_bitmap_rounded(
+        &mut self,
+        pos: BackendCoord,
+        (iw, ih): (u32, u32),
+        radius: f32,
+        src: &[u8],
+    ) -> Result<(), DrawingErrorKind<SkiaError>> {
+        if iw == 0 || ih == 0 {
+            return Ok(());
+        }
+
+        let img = self
+            .raster_image((iw, ih), src, self.bitmap_color_type, None)
+            .map_err(DrawingErrorKind::DrawingError)?;
+
+        let rect = Rect::from_xywh(pos.0 as f32, pos.1 as f32, iw as f32, ih as f32);
+
+        let rrect = RRect::new_rect_xy(rect, radius, radius);
+        let anti_alias = self.analytic_aa();
+
+        for canvas in self.canvases() {
+            canvas.save();
+            canvas.clip_rrect(rrect, ClipOp::Intersect, anti_alias);
+            canvas.draw_image(&img, pos, None);
+            canvas.restore();
+        }
+
+        Ok(())
+    }
+
+    fn arrow_head((tx, ty): (f32, f32), angle: f32, head_size: f32) -> Path {
+        // Half-angle of 30° between the shaft and each side of the head
+        let spread = std::f32::consts::FRAC_PI_6;
+        let (s1, c1) = (angle - spread).sin_cos();
+        let (s2, c2) = (angle + spread).sin_cos();
+
+        let mut path = Path::new();
+        path.move_to((tx, ty));
+        path.line_to((tx - head_size * c1, ty - head_size * s1));
+        path.line_to((tx - head_size * c2, ty - head_size * s2));
+        path.close();
+
+        path
+    }
+
+    /// Stroke a line from `from` to `to` with a filled arrowhead of length `head_size`
+    /// at `to`, and at `from` as well if `double` is set.
+    pub fn draw_arrow<S: BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+        head_size: f32,
+        double: bool,
+    ) {
+        let from = (from.0 as f32, from.1 as f32);
+        let to = (to.0 as f32, to.1 as f32);
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let len = dx.hypot(dy);
+
+        if len == 0.0 {
+            return;
+        }
+
+        let angle = dy.atan2(dx);
+
+        // Stop the shaft at the base of the heads so wide strokes don't poke out of the tips
+        let inset = (head_size * std::f32::consts::FRAC_PI_6.cos()).min(len);
+        let end_inset = if double { inset.min(len / 2.0) } else { inset };
+        let (ux, uy) = (dx / len, dy / len);
+        let start = if double {
+            (from.0 + ux * end_inset, from.1 + uy * end_inset)
+        } else {
+            from
+        };
+        let end = (to.0 - ux * end_inset, to.1 - uy * end_inset);
+
+        let mut heads = Self::arrow_head(to, angle, head_size);
+
+        if double {
+            let reversed = angle + std::f32::consts::PI;
+            heads.add_path(&Self::arrow_head(from, reversed, head_size), (0, 0), None);
+        }
+
+        let stroke = self.style_paint(style, false);
+        let fill = self.style_paint(style, true);
+
+        for canvas in self.canvases() {
+            canvas.draw_line(start, end, &stroke);
+            canvas.draw_path(&heads, &fill);
+        }
+    }
+
+    /// Fill a `rows` x `cols` grid of cells of size `cell_size` starting at `origin`,
+    /// coloring each cell by passing its value to `colormap`.
+    ///
+    /// `values` is in row-major order, i.e. the value of the cell in row `r` and column
+    /// `c` is `values[r * cols + c]`. Cells without a value are left empty.
+    pub fn draw_heatmap(
+        &mut self,
+        origin: BackendCoord,
+        (cell_w, cell_h): (u32, u32),
+        rows: usize,
+        cols: usize,
+        values: &[f32],
+        colormap: impl Fn(f32) -> Color,
+    ) {
+        let mut paths = ColorGroupedPaths::new();
+
+        for (i, value) in values.iter().take(rows * cols).enumerate() {
+            let (row, col) = (i / cols, i % cols);
+            let rect = Rect::from_xywh(
+                (origin.0 + (col as u32 * cell_w) as i32) as f32,
+                (origin.1 + (row as u32 * cell_h) as i32) as f32,
+                cell_w as f32,
+                cell_h as f32,
+            );
+
+            paths.get(colormap(*value)).add_rect(rect, None);
+        }
+
+        self.fill_color_grouped(paths, false);
+    }
+
+    // Each color's path takes a single draw call
+    fn fill_color_grouped(&mut self, paths: ColorGroupedPaths, anti_alias: bool) {
+        let mut paint = Paint::default();
+        paint.set_dither(self.dither).set_anti_alias(anti_alias);
+
+        if let Some(mode) = self.style.blend_mode {
+            paint.set_blend_mode(mode);
+        }
+
+        for ((a, r, g, b), path) in paths.0 {
+            paint.set_color(Color::from_argb(a, r, g, b));
+
+            for canvas in self.canvases() {
+                canvas.draw_path(&path, &paint);
+            }
+        }
+    }
+
+    /// Fill a calendar heatmap of `weeks` columns of seven rounded cells each, one per day,
+    /// coloring each cell by passing its value to `colormap`.
+    ///
+    /// `values` is in column-major order, i.e. the value of day `d` of week `w` is
+    /// `values[w * 7 + d]`. Days without a value are left empty. Cells are `cell_size`
+    /// pixels wide with `gap` pixels between them and corners rounded by a fifth of that.
+    pub fn draw_calendar_heatmap(
+        &mut self,
+        origin: BackendCoord,
+        cell_size: f32,
+        gap: f32,
+        values: &[f32],
+        weeks: usize,
+        colormap: impl Fn(f32) -> Color,
+    ) {
+        let radius = cell_size / 5.0;
+        let step = cell_size + gap;
+        let mut paths = ColorGroupedPaths::new();
+
+        for (i, value) in values.iter().take(weeks * 7).enumerate() {
+            let (week, day) = (i / 7, i % 7);
+            let rect = Rect::from_xywh(
+                origin.0 as f32 + week as f32 * step,
+                origin.1 as f32 + day as f32 * step,
+                cell_size,
+                cell_size,
+            );
+
+            paths
+                .get(colormap(*value))
+                .add_rrect(RRect::new_rect_xy(rect, radius, radius), None);
+        }
+
+        self.fill_color_grouped(paths, self.analytic_aa());
+    }
+
+    /// Draw `text` inside `box_rect` at the largest size up to `base_style`'s that fits in it,
+    /// aligned within the box according to the style's anchor.
+    ///
+    /// Returns the chosen size, in the same unit as [`BackendTextStyle::size`], so related
+    /// labels can be drawn at the same size.
+    pub fn draw_text_fit<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        base_style: &TStyle,
+        box_rect: Rect,
+    ) -> Result<f64, DrawingErrorKind<SkiaError>> {
+        // Line height rather than ink since that's what the text gets placed by
+        let fits = |size: f64| {
+            self.text_extent(text, base_style, size as f32 * FONT_EM_FACTOR)
+                .map(|(width, height)| width <= box_rect.width() && height <= box_rect.height())
+                .map_err(DrawingErrorKind::DrawingError)
+        };
+
+        let max = base_style.size();
+
+        let size = if fits(max)? {
+            max
+        } else {
+            let (mut lo, mut hi) = (0.0, max);
+
+            // Plenty of precision for pixel-sized fonts
+            for _ in 0..16 {
+                let mid = (lo + hi) / 2.0;
+
+                if fits(mid)? {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+
+            lo
+        };
+
+        let anchor = base_style.anchor();
+
+        let x = match anchor.h_pos {
+            HPos::Left => box_rect.left,
+            HPos::Right => box_rect.right,
+            HPos::Center => box_rect.center_x(),
+        };
+
+        let y = match anchor.v_pos {
+            VPos::Top => box_rect.top,
+            VPos::Center => box_rect.center_y(),
+            VPos::Bottom => box_rect.bottom,
+        };
+
+        let pos = (x.round() as i32, y.round() as i32);
+
+        // Scaled around the anchor so shaped and plain text shrink alike
+        let scale = if max > 0.0 { (size / max) as f32 } else { 1.0 };
+
+        for canvas in self.canvases() {
+            canvas.save();
+            canvas.translate(pos);
+            canvas.scale((scale, scale));
+            canvas.translate((-pos.0, -pos.1));
+        }
+
+        let res = self.draw_str_(text, base_style, base_style.color(), pos);
+
+        for canvas in self.canvases() {
+            canvas.restore();
+        }
+
+        res.map(|_| size)
+    }
+
+    fn erase_paint(&self) -> Paint {
+        let mut paint = Paint::default();
+        paint
+            .set_anti_alias(self.analytic_aa())
+            .set_blend_mode(BlendMode::Clear);
+
+        paint
+    }
+
+    /// Punch a transparent circular hole into what has been drawn so far.
+    ///
+    /// Only useful on surfaces with an alpha channel; opaque ones can't become transparent.
+    pub fn erase_circle(&mut self, center: BackendCoord, radius: f32) {
+        let paint = self.erase_paint();
+
+        for canvas in self.canvases() {
+            canvas.draw_circle(center, radius, &paint);
+        }
+    }
+
+    /// Punch a transparent rectangular hole into what has been drawn so far.
+    ///
+    /// Only useful on surfaces with an alpha channel; opaque ones can't become transparent.
+    pub fn erase_rect(&mut self, rect: Rect) {
+        let paint = self.erase_paint();
+
+        for canvas in self.canvases() {
+            canvas.draw_rect(rect, &paint);
+        }
+    }
+
+    /// Format `value` according to `format` and draw it like [`DrawingBackend::draw_text`].
+    pub fn draw_number<TStyle: BackendTextStyle>(
+        &mut self,
+        value: f64,
+        pos: BackendCoord,
+        style: &TStyle,
+        format: NumberFormat,
+    ) -> Result<(), DrawingErrorKind<SkiaError>> {
+        self.draw_str_(&format.format(value), style, style.color(), pos)
+    }
+
+    /// Darken the edges of `rect` with a radial gradient from transparent at its center to
+    /// `edge_color` at its corners.
+    ///
+    /// `strength` in `0.0..=1.0` is the fraction of the distance from the corners towards
+    /// the center that the gradient covers; nothing is drawn for `0.0`.
+    pub fn apply_vignette(&mut self, rect: Rect, edge_color: BackendColor, strength: f32) {
+        let strength = strength.clamp(0.0, 1.0);
+
+        if strength == 0.0 {
+            return;
+        }
+
+        // Opaque since the paint's alpha already applies on top of the shader
+        let edge = skia_color(edge_color).with_a(255);
+        let center = rect.center();
+        let radius = rect.width().hypot(rect.height()) / 2.0;
+
+        let shader = Shader::radial_gradient(
+            center,
+            radius,
+            [edge.with_a(0), edge].as_ref(),
+            [1.0 - strength, 1.0].as_ref(),
+            TileMode::Clamp,
+            self.gradient_flags(),
+            None,
+        );
+
+        let mut paint = self.paint(edge_color);
+        paint.set_shader(shader);
+
+        for canvas in self.canvases() {
+            canvas.draw_rect(rect, &paint);
+        }
+    }
+
+    /// Cover the canvas with a grid of dots `spacing` apart, e.g. for a graph-paper
+    /// background. The grid is shifted by `offset` to follow panning.
+    pub fn draw_dot_grid(
+        &mut self,
+        spacing: f32,
+        dot_radius: f32,
+        color: BackendColor,
+        offset: (f32, f32),
+    ) {
+        if spacing <= 0.0 {
+            return;
+        }
+
+        let (w, h) = (self.width as f32, self.height as f32);
+        let start_x = offset.0.rem_euclid(spacing);
+        let start_y = offset.1.rem_euclid(spacing);
+
+        let cols = (((w - start_x) / spacing).floor() + 1.0) as usize;
+        let rows = (((h - start_y) / spacing).floor() + 1.0) as usize;
+
+        let points: Vec<_> = (0..rows)
+            .flat_map(|row| {
+                let y = start_y + row as f32 * spacing;
+
+                (0..cols).map(move |col| Point::new(start_x + col as f32 * spacing, y))
+            })
+            .collect();
+
+        // Round caps turn each point into a dot of the stroke's diameter
+        let mut paint = self.paint(color);
+        paint
+            .set_stroke_cap(PaintCap::Round)
+            .set_stroke_width(dot_radius * 2.0);
+
+        for canvas in self.canvases() {
+            canvas.draw_points(PointMode::Points, &points, &paint);
+        }
+    }
+
+    /// Serialize `picture`, e.g. one returned by [`Self::finish_tee`], so that it can be
+    /// persisted and later drawn with [`Self::replay_serialized`].
+    pub fn serialize_picture(picture: &Picture) -> Vec<u8> {
+        picture.serialize().as_bytes().to_vec()
+    }
+
+    /// Deserialize a picture produced by [`Self::serialize_picture`] and draw it.
+    ///
+    /// Skia's picture deserializer isn't hardened against malicious input, so `bytes` must
+    /// come from a trusted source such as this application's own cache.
+    pub fn replay_serialized(&mut self, bytes: &[u8]) -> Result<(), SkiaError> {
+        let picture = Picture::from_bytes(bytes).ok_or(SkiaError::Deserialize)?;
+
+        for canvas in self.canvases() {
+            canvas.draw_picture(&picture, None, None);
+        }
+
+        Ok(())
+    }
+
+    /// Stroke a box-and-whisker element centered on `x` along the category axis: a box of
+    /// `width` from `q1` to `q3` with a median line, and whiskers with caps reaching
+    /// `whisker_low` and `whisker_high`.
+    ///
+    /// The values are pixel positions on the value axis, which is vertical unless
+    /// `horizontal` is set, in which case `x` is a y position instead.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_boxplot<S: BackendStyle>(
+        &mut self,
+        x: f32,
+        width: f32,
+        q1: f32,
+        median: f32,
+        q3: f32,
+        whisker_low: f32,
+        whisker_high: f32,
+        style: &S,
+        horizontal: bool,
+    ) {
+        // Maps (category axis, value axis) to canvas coordinates
+        let point = |along: f32, value: f32| {
+            let (x, y) = if horizontal {
+                (value, along)
+            } else {
+                (along, value)
+            };
+
+            self.rounding.point((x, y))
+        };
+
+        let half = width / 2.0;
+        let cap = width / 4.0;
+
+        // Closed so the box corners are joined
+        let mut path = Path::new();
+        path.move_to(point(x - half, q1));
+        path.line_to(point(x + half, q1));
+        path.line_to(point(x + half, q3));
+        path.line_to(point(x - half, q3));
+        path.close();
+
+        let mut segment = |along: (f32, f32), value: (f32, f32)| {
+            path.move_to(point(along.0, value.0));
+            path.line_to(point(along.1, value.1));
+        };
+
+        segment((x - half, x + half), (median, median));
+
+        // Whiskers and their caps
+        segment((x, x), (q1, whisker_low));
+        segment((x, x), (q3, whisker_high));
+        segment((x - cap, x + cap), (whisker_low, whisker_low));
+        segment((x - cap, x + cap), (whisker_high, whisker_high));
+
+        let paint = self.style_paint(style, false);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &paint);
+        }
+    }
+
+    /// Draw the shape described by SVG path data, scaled so that its larger side spans
+    /// `size` pixels and centered on `center`.
+    pub fn draw_svg_marker<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        path_data: &str,
+        size: f32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<SkiaError>> {
+        // The parser takes a C string so interior nul bytes can't be passed through
+        let path = (!path_data.contains('\0'))
+            .then(|| parse_path::from_svg(path_data))
+            .flatten()
+            .ok_or(DrawingErrorKind::DrawingError(SkiaError::SvgPath))?;
+
+        let bounds = path.bounds();
+        let extent = bounds.width().max(bounds.height());
+        let scale = if extent > 0.0 { size / extent } else { 1.0 };
+
+        let mut matrix = Matrix::translate((center.0 as f32, center.1 as f32));
+        matrix.pre_scale((scale, scale), None);
+        matrix.pre_translate((-bounds.center_x(), -bounds.center_y()));
+
+        let path = path.with_transform(&matrix);
+        let paint = self.style_paint(style, fill);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &paint);
+        }
+
+        Ok(())
+    }
+
+    /// Fill `rect` with a vertical gradient from a lighter shade of `base_color` at the top
+    /// to `base_color` at the bottom, with a translucent white sheen over its upper part.
+    pub fn draw_glossy_bar(&mut self, rect: Rect, base_color: BackendColor) {
+        // Opaque since the paint's alpha already applies on top of the shader
+        let base = skia_color(base_color).with_a(255);
+        let lighten = |c: u8| c + ((255 - c) as f32 * 0.35) as u8;
+        let light = Color::from_rgb(lighten(base.r()), lighten(base.g()), lighten(base.b()));
+
+        let top = (rect.center_x(), rect.top);
+        let bottom = (rect.center_x(), rect.bottom);
+
+        let mut body = self.paint(base_color);
+        body.set_shader(Shader::linear_gradient(
+            (top, bottom),
+            [light, base].as_ref(),
+            None,
+            TileMode::Clamp,
+            self.gradient_flags(),
+            None,
+        ));
+
+        // The sheen fades out over the upper 40% of the bar
+        let mut sheen_rect = rect;
+        sheen_rect.bottom = rect.top + rect.height() * 0.4;
+
+        let mut sheen = self.paint(base_color);
+        sheen.set_shader(Shader::linear_gradient(
+            (top, (rect.center_x(), sheen_rect.bottom)),
+            [Color::WHITE.with_a(90), Color::WHITE.with_a(0)].as_ref(),
+            None,
+            TileMode::Clamp,
+            self.gradient_flags(),
+            None,
+        ));
+
+        for canvas in self.canvases() {
+            canvas.draw_rect(rect, &body);
+            canvas.draw_rect(sheen_rect, &sheen);
+        }
+    }
+
+    /// Fill the full-width band between `y_low` and `y_high` with `color`, e.g. to shade a
+    /// normal range. Translucent colors combine with the active blend mode.
+    pub fn draw_reference_band(&mut self, y_low: f32, y_high: f32, color: BackendColor) {
+        let rect = Rect::new(0.0, y_low, self.width as f32, y_high).sorted();
+        self.fill_band(rect, color);
+    }
+
+    /// Like [`Self::draw_reference_band`] but for the full-height band between `x_low`
+    /// and `x_high`.
+    pub fn draw_reference_band_vertical(&mut self, x_low: f32, x_high: f32, color: BackendColor) {
+        let rect = Rect::new(x_low, 0.0, x_high, self.height as f32).sorted();
+        self.fill_band(rect, color);
+    }
+
+    fn fill_band(&mut self, rect: Rect, color: BackendColor) {
+        let paint = self.paint(color);
+
+        for canvas in self.canvases() {
+            canvas.draw_rect(rect, &paint);
+        }
+    }
+
+    /// Draw text in black or white, whichever contrasts better with `bg_color`, ignoring
+    /// the color of `style`.
+    ///
+    /// Uses the WCAG relative luminance of the background, switching to white text below
+    /// the luminance where both have the same contrast ratio.
+    pub fn draw_text_auto_contrast<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: BackendCoord,
+        bg_color: BackendColor,
+    ) -> Result<(), DrawingErrorKind<SkiaError>> {
+        let linear = |c: u8| {
+            let c = c as f64 / 255.0;
+
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        let (r, g, b) = bg_color.rgb;
+        let luminance = 0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b);
+
+        // Solves (L + 0.05) / 0.05 == 1.05 / (L + 0.05), the contrast against black and white
+        let rgb = if luminance > 0.179 {
+            (0, 0, 0)
+        } else {
+            (255, 255, 255)
+        };
+
+        let color = BackendColor {
+            alpha: style.color().alpha,
+            rgb,
+        };
+
+        self.draw_str_(text, style, color, pos)
+    }
+
+    /// Draw `overview` scaled into `inset_rect` and stroke `viewport_rect`, given in the
+    /// overview's pixel coordinates, on top of it with `border_style`.
+    pub fn draw_minimap<S: BackendStyle>(
+        &mut self,
+        inset_rect: Rect,
+        overview: &Image,
+        viewport_rect: Rect,
+        border_style: &S,
+    ) {
+        let sx = inset_rect.width() / overview.width().max(1) as f32;
+        let sy = inset_rect.height() / overview.height().max(1) as f32;
+
+        let mut viewport = Rect::new(
+            inset_rect.left + viewport_rect.left * sx,
+            inset_rect.top + viewport_rect.top * sy,
+            inset_rect.left + viewport_rect.right * sx,
+            inset_rect.top + viewport_rect.bottom * sy,
+        );
+
+        // Keep the marker inside the inset when the viewport extends past the data
+        if !viewport.intersect(inset_rect) {
+            viewport = Rect::new_empty();
+        }
+
+        let border = self.style_paint(border_style, false);
+
+        for canvas in self.canvases() {
+            canvas.draw_image_rect(overview, None, inset_rect, &Paint::default());
+
+            if !viewport.is_empty() {
+                canvas.draw_rect(viewport, &border);
+            }
+        }
+    }
+
+    /// Fill a stroke along `points` whose width varies per vertex, interpolating linearly
+    /// between them, e.g. for tapered flow lines.
+    ///
+    /// Sides are joined with miters, which are limited to twice the half-width at sharp
+    /// turns so they don't spike outwards.
+    pub fn draw_variable_width_path(
+        &mut self,
+        points: &[(BackendCoord, f32)],
+        color: BackendColor,
+    ) {
+        let mut vertices: Vec<((f32, f32), f32)> = Vec::with_capacity(points.len());
+
+        // Repeated points have no direction to offset from
+        for ((x, y), width) in points {
+            let point = (*x as f32, *y as f32);
+
+            if vertices.last().map(|(last, _)| *last) != Some(point) {
+                vertices.push((point, *width));
+            }
+        }
+
+        if vertices.len() < 2 {
+            return;
+        }
+
+        let direction = |from: (f32, f32), to: (f32, f32)| {
+            let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+            let len = dx.hypot(dy);
+
+            (dx / len, dy / len)
+        };
+
+        let last = vertices.len() - 1;
+        let mut left = Vec::with_capacity(vertices.len());
+        let mut right = Vec::with_capacity(vertices.len());
+
+        for (i, &(point, width)) in vertices.iter().enumerate() {
+            let incoming = (i > 0).then(|| direction(vertices[i - 1].0, point));
+            let outgoing = (i < last).then(|| direction(point, vertices[i + 1].0));
+
+            let (tx, ty, miter) = match (incoming, outgoing) {
+                (Some(a), Some(b)) => {
+                    let (sx, sy) = (a.0 + b.0, a.1 + b.1);
+                    let len = sx.hypot(sy);
+
+                    if len < f32::EPSILON {
+                        // The path doubles back on itself
+                        (a.0, a.1, 1.0)
+                    } else {
+                        let (tx, ty) = (sx / len, sy / len);
+
+                        // The offset along the miter grows with 1 / cos of half the turn
+                        let cos = tx * b.0 + ty * b.1;
+
+                        (tx, ty, (1.0 / cos).min(2.0))
+                    }
+                }
+                (Some(d), None) | (None, Some(d)) => (d.0, d.1, 1.0),
+                (None, None) => unreachable!(),
+            };
+
+            let offset = width / 2.0 * miter;
+            let (nx, ny) = (-ty * offset, tx * offset);
+
+            left.push((point.0 + nx, point.1 + ny));
+            right.push((point.0 - nx, point.1 - ny));
+        }
+
+        let mut path = Path::new();
+        path.move_to(left[0]);
+
+        for point in left.iter().skip(1).chain(right.iter().rev()) {
+            path.line_to(*point);
+        }
+
+        path.close();
+
+        let paint = self.paint(color);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &paint);
+        }
+    }
+
+    /// Stroke the two parallel zigzag lines marking a break in an axis of the given
+    /// `orientation` at `pos`. The zigzags extend `length` pixels across the axis.
+    pub fn draw_axis_break<S: BackendStyle>(
+        &mut self,
+        pos: BackendCoord,
+        length: f32,
+        orientation: Orientation,
+        style: &S,
+    ) {
+        const TEETH: usize = 4;
+
+        let gap = length / 4.0;
+        let amplitude = length / 8.0;
+        let step = length / TEETH as f32;
+        let (px, py) = (pos.0 as f32, pos.1 as f32);
+
+        // Lay the zigzags out for a horizontal axis as (along axis, across axis) offsets
+        let point = |along: f32, across: f32| match orientation {
+            Orientation::Horizontal => (px + along, py + across),
+            Orientation::Vertical => (px + across, py + along),
+        };
+
+        let mut path = Path::new();
+
+        for center in [-gap / 2.0, gap / 2.0] {
+            path.move_to(point(center, -length / 2.0));
+
+            for i in 1..=TEETH {
+                let side = if i % 2 == 1 { amplitude } else { -amplitude };
+                let across = -length / 2.0 + i as f32 * step;
+                let along = if i == TEETH { center } else { center + side };
+
+                path.line_to(point(along, across));
+            }
+        }
+
+        let paint = self.style_paint(style, false);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &paint);
+        }
+    }
+
+    /// Fill a rounded panel with `bg_color` and stroke its border, to be drawn behind
+    /// legend entries. Translucent colors combine with the active blend mode.
+    pub fn draw_legend_panel<S: BackendStyle>(
+        &mut self,
+        rect: Rect,
+        bg_color: BackendColor,
+        border_style: &S,
+        corner_radius: f32,
+    ) {
+        let rrect = RRect::new_rect_xy(rect, corner_radius, corner_radius);
+
+        let fill = self.paint(bg_color);
+        let border = self.style_paint(border_style, false);
+
+        for canvas in self.canvases() {
+            canvas.draw_rrect(rrect, &fill);
+            canvas.draw_rrect(rrect, &border);
+        }
+    }
+
+    /// Fill the region inside `outer` but outside each of `holes` with `color`, e.g. a band
+    /// between two isolines of a contour plot.
+    ///
+    /// The rings are combined with the even-odd rule, so their winding directions don't
+    /// matter.
+    pub fn fill_contour_band(
+        &mut self,
+        outer: &[BackendCoord],
+        holes: &[Vec<BackendCoord>],
+        color: BackendColor,
+    ) {
+        let mut path = Path::new();
+        path.set_fill_type(PathFillType::EvenOdd);
+
+        for ring in std::iter::once(outer).chain(holes.iter().map(Vec::as_slice)) {
+            if ring.len() < 3 {
+                continue;
+            }
+
+            path.add_path(&Self::polyline(ring.iter().copied()), (0, 0), None);
+            path.close();
+        }
+
+        let paint = self.paint(color);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &paint);
+        }
+    }
+
+    /// Fill an ellipse with radii `radii` around `center` and stroke its border.
+    pub fn draw_ellipse_filled_stroked(
+        &mut self,
+        center: BackendCoord,
+        (rx, ry): (f32, f32),
+        fill_color: BackendColor,
+        stroke_color: BackendColor,
+        stroke_width: f32,
+    ) {
+        let oval = Rect::from_xywh(
+            center.0 as f32 - rx,
+            center.1 as f32 - ry,
+            rx * 2.0,
+            ry * 2.0,
+        );
+
+        let fill = self.paint(fill_color);
+
+        let mut stroke = fill.clone();
+        stroke
+            .set_color(skia_color(stroke_color))
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(stroke_width);
+
+        for canvas in self.canvases() {
+            canvas.draw_oval(oval, &fill);
+            canvas.draw_oval(oval, &stroke);
+        }
+    }
+
+    /// Draw a circular lens of `radius` around `center` showing `source` magnified by
+    /// `zoom`, and stroke its border with `border_style`.
+    ///
+    /// `source_center` is the point in `source`'s pixel coordinates that appears at the
+    /// lens center; pixel `p` of `source` lands at `center + (p - source_center) * zoom`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_magnifier<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: f32,
+        source: &Image,
+        zoom: f32,
+        source_center: (f32, f32),
+        border_style: &S,
+    ) {
+        let (cx, cy) = (center.0 as f32, center.1 as f32);
+
+        let mut lens = Path::new();
+        lens.add_circle((cx, cy), radius, None);
+
+        let dst = Rect::from_xywh(
+            cx - source_center.0 * zoom,
+            cy - source_center.1 * zoom,
+            source.width() as f32 * zoom,
+            source.height() as f32 * zoom,
+        );
+
+        let sampling = SamplingOptions::new(FilterMode::Linear, MipmapMode::None);
+        let border = self.style_paint(border_style, false);
+        let anti_alias = self.analytic_aa();
+
+        for canvas in self.canvases() {
+            canvas.save();
+            canvas.clip_path(&lens, ClipOp::Intersect, anti_alias);
+            canvas.draw_image_rect_with_sampling_options(
+                source,
+                None,
+                dst,
+                sampling,
+                &Paint::default(),
+            );
+            canvas.restore();
+
+            canvas.draw_path(&lens, &border);
+        }
+    }
+
+    /// Confine all drawing to the rectangle between `upper_left` and `bottom_right` until
+    /// the matching [`Self::pop_clip`]. Nested clips intersect.
+    ///
+    /// Useful to scope a plotters chart to part of a larger canvas without strokes bleeding
+    /// into neighboring regions.
+    pub fn push_clip_rect(&mut self, upper_left: BackendCoord, bottom_right: BackendCoord) {
+        let rect = Rect::new(
+            upper_left.0 as f32,
+            upper_left.1 as f32,
+            bottom_right.0 as f32,
+            bottom_right.1 as f32,
+        );
+
+        for canvas in self.canvases() {
+            canvas.save();
+            canvas.clip_rect(rect, ClipOp::Intersect, false);
+        }
+
+        self.clip_depth += 1;
+    }
+
+    /// Remove the clip of the most recent [`Self::push_clip_rect`]. Does nothing if there
+    /// is none.
+    pub fn pop_clip(&mut self) {
+        if self.clip_depth == 0 {
+            return;
+        }
+
+        for canvas in self.canvases() {
+            canvas.restore();
+        }
+
+        self.clip_depth -= 1;
+
+        if self.plot_clip_level == Some(self.clip_depth) {
+            self.plot_clip_level = None;
+        }
+    }
+
+    /// Record the chart's plotting area for [`Self::clip_to_plot_area`].
+    ///
+    /// If the clip is currently applied it's replaced by one for the new area.
+    pub fn set_plot_area(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+    ) -> &mut Self {
+        self.plot_area = Some((upper_left, bottom_right));
+
+        if self.plot_clip_level.is_some() {
+            self.clip_to_plot_area(false).clip_to_plot_area(true);
+        }
+
+        self
+    }
+
+    /// Confine subsequent draws to the area set with [`Self::set_plot_area`], e.g. so series
+    /// don't spill into the chart's margins. Does nothing without a plot area.
+    ///
+    /// The clip lives on the same stack as [`Self::push_clip_rect`]: clips pushed while it
+    /// is applied intersect with it, and disabling it also pops those.
+    pub fn clip_to_plot_area(&mut self, enabled: bool) -> &mut Self {
+        match (enabled, self.plot_clip_level, self.plot_area) {
+            (true, None, Some((upper_left, bottom_right))) => {
+                self.plot_clip_level = Some(self.clip_depth);
+                self.push_clip_rect(upper_left, bottom_right);
+            }
+            (false, Some(level), _) => {
+                while self.clip_depth > level {
+                    self.pop_clip();
+                }
+            }
+            _ => {}
+        }
+
+        self
+    }
+
+    /// Draw several labels sharing `style`, e.g. axis tick labels, resolving the font once
+    /// instead of per label.
+    pub fn draw_texts<TStyle: BackendTextStyle>(
+        &mut self,
+        items: &[(BackendCoord, &str)],
+        style: &TStyle,
+    ) -> Result<(), DrawingErrorKind<SkiaError>> {
+        // Shaped text is laid out per paragraph anyway
+        #[cfg(feature = "shaping")]
+        if self.font_collection.is_some() {
+            for (pos, text) in items {
+                self.draw_str_(text, style, style.color(), *pos)?;
+            }
+
+            return Ok(());
+        }
+
+        let paint = self.text_paint(style.color());
+        let font = self.font(style).map_err(DrawingErrorKind::DrawingError)?;
+        let anchor = style.anchor();
+
+        for (pos, text) in items {
+            let origin = Self::text_origin(&font, &paint, text, anchor, *pos);
+
+            for canvas in self.canvases() {
+                canvas.draw_str(text, origin, &font, &paint);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draw a donut chart: starting at the top and going clockwise, each segment gets a
+    /// share of the ring proportional to its value, filled with its color and outlined
+    /// with `style`.
+    ///
+    /// Returns each segment's mid-angle, in degrees clockwise from the positive x-axis like
+    /// [`Self::draw_annular_wedge`], for placing labels. Segments with non-positive values
+    /// take up no space.
+    pub fn draw_donut<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        inner_r: f32,
+        outer_r: f32,
+        segments: &[(f32, Color)],
+        style: &S,
+    ) -> Vec<f32> {
+        let total: f32 = segments.iter().map(|(value, _)| value.max(0.0)).sum();
+        let center = (center.0 as f32, center.1 as f32);
+        let stroke = self.style_paint(style, false);
+        let mut fill = self.paint(style.color());
+
+        let mut start = -90.0;
+        let mut mid_angles = Vec::with_capacity(segments.len());
+
+        for (value, color) in segments {
+            let sweep = if total > 0.0 {
+                value.max(0.0) / total * 360.0
+            } else {
+                0.0
+            };
+
+            mid_angles.push(start + sweep / 2.0);
+
+            if sweep > 0.0 {
+                let path = Self::annular_wedge_path(center, inner_r, outer_r, start, sweep);
+                fill.set_color(*color);
+
+                for canvas in self.canvases() {
+                    canvas.draw_path(&path, &fill);
+                    canvas.draw_path(&path, &stroke);
+                }
+            }
+
+            start += sweep;
+        }
+
+        mid_angles
+    }
+
+    /// Color of the shadows drawn by [`Self::draw_marker_shadowed`]. Defaults to black at
+    /// 40% opacity.
+    pub fn set_shadow_color(&mut self, color: BackendColor) -> &mut Self {
+        self.shadow_color = color;
+
+        self
+    }
+
+    /// Draw a marker of `shape` filling a `size` pixel box around `center` over a copy
+    /// offset by `shadow_offset` and blurred with sigma `shadow_blur`.
+    pub fn draw_marker_shadowed(
+        &mut self,
+        center: BackendCoord,
+        shape: MarkerShape,
+        size: f32,
+        fill: BackendColor,
+        shadow_offset: (f32, f32),
+        shadow_blur: f32,
+    ) {
+        self.draw_markers_shadowed(&[center], shape, size, fill, shadow_offset, shadow_blur);
+    }
+
+    /// Like [`Self::draw_marker_shadowed`] for many markers.
+    ///
+    /// All shadows are drawn before all markers, in one draw call each, so markers never
+    /// get covered by shadows of their neighbors.
+    pub fn draw_markers_shadowed(
+        &mut self,
+        centers: &[BackendCoord],
+        shape: MarkerShape,
+        size: f32,
+        fill: BackendColor,
+        shadow_offset: (f32, f32),
+        shadow_blur: f32,
+    ) {
+        let mut markers = Path::new();
+
+        for (x, y) in centers {
+            shape.add_to(&mut markers, (*x as f32, *y as f32), size);
+        }
+
+        let shadows = markers.with_offset(shadow_offset);
+
+        let mut shadow_paint = self.paint(self.shadow_color);
+
+        if shadow_blur > 0.0 {
+            shadow_paint.set_mask_filter(MaskFilter::blur(BlurStyle::Normal, shadow_blur, false));
+        }
+
+        let marker_paint = self.paint(fill);
+
+        for canvas in self.canvases() {
+            canvas.draw_path(&shadows, &shadow_paint);
+            canvas.draw_path(&markers, &marker_paint);
+        }
+    }
+
+    /// Draw the horizontal connector of a waterfall chart at the running total `y`, from
+    /// the edge of one bar at `from_x` to the next one at `to_x`.
+    ///
+    /// Uses the dash set with e.g. [`Self::set_dash`], or dashes three stroke widths long
+    /// if none is set.
+    pub fn draw_waterfall_connector<S: BackendStyle>(
+        &mut self,
+        from_x: f32,
+        to_x: f32,
+        y: f32,
+        style: &S,
+    ) {
+        let mut paint = self.style_paint(style, false);
+
+        if self.style.dash.is_none() {
+            let dash = Dash {
+                intervals: vec![3.0, 3.0],
+                phase: 0.0,
+                relative: true,
+            };
+
+            paint.set_path_effect(dash.path_effect(paint.stroke_width(), self.style.dash_scale));
+        }
+
+        let from = self.rounding.point((from_x, y));
+        let to = self.rounding.point((to_x, y));
+
+        for canvas in self.canvases() {
+            canvas.draw_line(from, to, &paint);
+        }
+    }
+
+    /// Draw a radar chart: a grid of `axes` spokes and four concentric rings stroked with
+    /// `grid_style`, under the polygon through `values` filled with `fill_style`.
+    ///
+    /// The first axis points up and the others follow clockwise. Values are fractions of
+    /// `radius` in `0.0..=1.0`, missing ones count as `0.0`. Returns the polygon's vertices.
+    pub fn draw_radar<F: BackendStyle, G: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: f32,
+        axes: usize,
+        values: &[f32],
+        fill_style: &F,
+        grid_style: &G,
+    ) -> Vec<(f32, f32)> {
+        const RINGS: usize = 4;
+
+        if axes < 3 {
+            return Vec::new();
+        }
+
+        let (cx, cy) = (center.0 as f32, center.1 as f32);
+
+        let point = |axis: usize, r: f32| {
+            let angle =
+                axis as f32 / axes as f32 * std::f32::consts::TAU - std::f32::consts::FRAC_PI_2;
+            let (sin, cos) = angle.sin_cos();
+
+            (cx + r * cos, cy + r * sin)
+        };
+
+        let mut grid = Path::new();
+
+        for axis in 0..axes {
+            grid.move_to((cx, cy));
+            grid.line_to(point(axis, radius));
+        }
+
+        for ring in 1..=RINGS {
+            let r = radius * ring as f32 / RINGS as f32;
+            grid.move_to(point(0, r));
+
+            for axis in 1..axes {
+                grid.line_to(point(axis, r));
+            }
+
+            grid.close();
+        }
+
+        let vertices: Vec<_> = (0..axes)
+            .map(|axis| {
+                let value = values.get(axis).copied().unwrap_or(0.0).clamp(0.0, 1.0);
+
+                point(axis, radius * value)
+            })
+            .collect();
+
+        let mut polygon = Path::new();
+        polygon.add_poly(
+            &vertices.iter().map(|&p| Point::from(p)).collect::<Vec<_>>(),
+            true,
+        );
+
+        let grid_paint = self.style_paint(grid_style, false);
+        let fill_paint = self.style_paint(fill_style, true);
 
-#[derive(Debug)]
-pub enum SkiaError {
-    Typeface,
-    ImageFromRaster,
-}
+        for canvas in self.canvases() {
+            canvas.draw_path(&grid, &grid_paint);
+            canvas.draw_path(&polygon, &fill_paint);
+        }
 
-impl Display for SkiaError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        Debug::fmt(self, f)
+        vertices
     }
-}
 
-impl StdError for SkiaError {}
+    /// Fill one ring level of a sunburst chart: starting at the top and going clockwise,
+    /// each segment gets a share of the ring proportional to its value.
+    ///
+    /// Wedges of the same color are filled together. A single segment fills the whole
+    /// ring, or a disc for an `inner_r` of `0.0`. Returns each segment's start and end
+    /// angle in degrees clockwise from the positive x-axis, for laying out the next level.
+    pub fn draw_sunburst_level(
+        &mut self,
+        center: BackendCoord,
+        inner_r: f32,
+        outer_r: f32,
+        segments: &[(f32, Color)],
+    ) -> Vec<(f32, f32)> {
+        let total: f32 = segments.iter().map(|(value, _)| value.max(0.0)).sum();
+        let center = (center.0 as f32, center.1 as f32);
+
+        let mut paths = ColorGroupedPaths::new();
+        let mut start = -90.0;
+        let mut bounds = Vec::with_capacity(segments.len());
+
+        for &(value, color) in segments {
+            let sweep = if total > 0.0 {
+                value.max(0.0) / total * 360.0
+            } else {
+                0.0
+            };
+
+            bounds.push((start, start + sweep));
+
+            if sweep > 0.0 {
+                let wedge = Self::annular_wedge_path(center, inner_r, outer_r, start, sweep);
+                paths.get(color).add_path(&wedge, (0, 0), None);
+            }
 
-impl<'a> SkiaBackend<'a> {
-    pub fn new(canvas: &'a mut Canvas, w: u32, h: u32) -> Self {
-        Self {
-            canvas,
-            width: w,
-            height: h,
-            blend_mode: None,
+            start += sweep;
         }
-    }
 
-    pub fn set_blend_mode(&mut self, blend_mode: Option<BlendMode>) -> &mut Self {
-        self.blend_mode = blend_mode;
+        self.fill_color_grouped(paths, self.analytic_aa());
 
-        self
+        bounds
     }
 
-    fn paint(&self, color: BackendColor) -> Paint {
-        let alpha = (color.alpha * 255.0) as u8;
-        let (r, g, b) = color.rgb;
-        let color = Color::from_argb(alpha, r, g, b);
+    /// Fill the whole canvas with the `src` image blurred with `sigma`, e.g. a photo to
+    /// draw a chart over, so call it before drawing the chart.
+    ///
+    /// `src` is read like in [`DrawingBackend::blit_bitmap`]. The image keeps its aspect
+    /// ratio, is centered and gets cropped to cover the canvas.
+    pub fn draw_blurred_backdrop(
+        &mut self,
+        src: &[u8],
+        (iw, ih): (u32, u32),
+        sigma: f32,
+    ) -> Result<(), DrawingErrorKind<SkiaError>> {
+        if iw == 0 || ih == 0 {
+            return Ok(());
+        }
+
+        let image = self
+            .raster_image((iw, ih), src, self.bitmap_color_type, None)
+            .map_err(DrawingErrorKind::DrawingError)?;
+
+        let (w, h) = (self.width as f32, self.height as f32);
+        let scale = (w / iw as f32).max(h / ih as f32);
+        let (dw, dh) = (iw as f32 * scale, ih as f32 * scale);
+        let dst = Rect::from_xywh((w - dw) / 2.0, (h - dh) / 2.0, dw, dh);
 
         let mut paint = Paint::default();
-        paint.set_color(color);
+        paint.set_dither(self.dither);
+
+        // Clamping keeps the blur from fading the image's edges into transparency
+        if sigma > 0.0 {
+            paint.set_image_filter(image_filters::blur(
+                (sigma, sigma),
+                TileMode::Clamp,
+                None,
+                None,
+            ));
+        }
 
-        if let Some(mode) = self.blend_mode {
-            paint.set_blend_mode(mode);
+        let sampling = SamplingOptions::new(FilterMode::Linear, MipmapMode::None);
+
+        for canvas in self.canvases() {
+            canvas.save();
+            canvas.clip_rect(Rect::from_wh(w, h), ClipOp::Intersect, false);
+            canvas.draw_image_rect_with_sampling_options(&image, None, dst, sampling, &paint);
+            canvas.restore();
         }
 
-        paint
+        Ok(())
     }
 
-    // fn font<TStyle: BackendTextStyle>(font: &TStyle) -> Result<Font, SkiaError> {
-    //     let font_style = match font.style() {
-    //         PFontStyle::Normal => FontStyle::new(Weight::NORMAL, Width::NORMAL, Slant::Upright),
-    //         PFontStyle::Oblique => FontStyle::new(Weight::NORMAL, Width::NORMAL, Slant::Oblique),
-    //         PFontStyle::Italic => FontStyle::new(Weight::NORMAL, Width::NORMAL, Slant::Italic),
-    //         PFontStyle::Bold => FontStyle::new(Weight::BOLD, Width::NORMAL, Slant::Upright),
-    //     };
+    /// Fill the polygon through `vert` with `image`, repeated in both directions.
+    ///
+    /// `transform` maps the image's pixel coordinates onto the canvas, so it positions,
+    /// scales and rotates the texture, e.g. to line a satellite image up with map
+    /// coordinates. Pass [`Matrix::default`] to draw the image at its own size from the
+    /// canvas origin.
+    pub fn fill_polygon_textured<I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        image: &Image,
+        transform: Matrix,
+    ) {
+        let mut path = Self::polyline(vert);
+        path.close();
 
-    //     let typeface =
-    //         Typeface::new(font.family().as_str(), font_style).ok_or(SkiaError::Typeface)?;
+        let sampling = SamplingOptions::new(FilterMode::Linear, MipmapMode::None);
 
-    //     Ok(Font::new(typeface, Some(font.size() as f32 * 0.83)))
-    // }
+        let mut paint = Paint::default();
+        paint
+            .set_dither(self.dither)
+            .set_anti_alias(self.analytic_aa())
+            .set_shader(image.to_shader(
+                (TileMode::Repeat, TileMode::Repeat),
+                sampling,
+                &transform,
+            ));
+
+        if let Some(mode) = self.style.blend_mode {
+            paint.set_blend_mode(mode);
+        }
 
-    fn draw_path_<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        for canvas in self.canvases() {
+            canvas.draw_path(&path, &paint);
+        }
+    }
+
+    /// Stroke a step line through `points` as a single path of alternating horizontal and
+    /// vertical segments.
+    ///
+    /// With `step_before` each point's value starts right after the previous point, i.e.
+    /// the line rises or falls first and then runs to the point. Otherwise it runs at the
+    /// previous point's value and steps at the point itself.
+    pub fn draw_step_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
         &mut self,
-        path: I,
+        points: I,
         style: &S,
-        filled: bool,
+        step_before: bool,
     ) {
-        let mut paint = self.paint(style.color());
-
-        paint
-            .set_stroke_width(style.stroke_width() as f32)
-            .set_anti_alias(true);
+        let mut prev: Option<BackendCoord> = None;
+        let mut steps = Vec::new();
+
+        for point in points {
+            if let Some((x, y)) = prev {
+                steps.push(if step_before {
+                    (x, point.1)
+                } else {
+                    (point.0, y)
+                });
+            }
 
-        if filled {
-            paint.set_style(PaintStyle::Fill);
-        } else {
-            paint.set_style(PaintStyle::Stroke);
+            steps.push(point);
+            prev = Some(point);
         }
 
-        let mut points = path.into_iter();
-        let mut path = Path::new();
+        self.draw_path_(steps, style, false);
+    }
+}
 
-        if let Some(point) = points.next() {
-            path.move_to(point);
+fn font_style(style: PFontStyle) -> FontStyle {
+    match style {
+        PFontStyle::Normal => FontStyle::new(Weight::NORMAL, Width::NORMAL, Slant::Upright),
+        PFontStyle::Oblique => FontStyle::new(Weight::NORMAL, Width::NORMAL, Slant::Oblique),
+        PFontStyle::Italic => FontStyle::new(Weight::NORMAL, Width::NORMAL, Slant::Italic),
+        PFontStyle::Bold => FontStyle::new(Weight::BOLD, Width::NORMAL, Slant::Upright),
+    }
+}
 
-            for point in points {
-                path.line_to(point);
+// Liang-Barsky: the part of the segment from `p0` to `p1` inside `bounds`, if any
+fn clip_segment(
+    bounds: Rect,
+    (x0, y0): (f32, f32),
+    (x1, y1): (f32, f32),
+) -> Option<((f32, f32), (f32, f32))> {
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let (mut t0, mut t1) = (0.0_f32, 1.0_f32);
+
+    let edges = [
+        (-dx, x0 - bounds.left),
+        (dx, bounds.right - x0),
+        (-dy, y0 - bounds.top),
+        (dy, bounds.bottom - y0),
+    ];
+
+    for (p, q) in edges {
+        if p == 0.0 {
+            // Parallel to this edge, so either fully outside or irrelevant
+            if q < 0.0 {
+                return None;
             }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
         }
 
-        self.canvas.draw_path(&path, &paint);
+        if t0 > t1 {
+            return None;
+        }
     }
+
+    Some(((x0 + t0 * dx, y0 + t0 * dy), (x0 + t1 * dx, y0 + t1 * dy)))
+}
+
+fn skia_color(color: BackendColor) -> Color {
+    let alpha = (color.alpha * 255.0) as u8;
+    let (r, g, b) = color.rgb;
+
+    Color::from_argb(alpha, r, g, b)
 }
 
 impl<'a> DrawingBackend for SkiaBackend<'a> {
@@ -125,6 +3043,17 @@ impl<'a> DrawingBackend for SkiaBackend<'a> {
 
     #[inline]
     fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        #[cfg(feature = "gpu")]
+        if let Target::Surface(ref mut surface) = self.target {
+            if let Some(mut context) = surface.direct_context() {
+                context.flush_and_submit_surface(surface, None);
+            }
+        }
+
+        if self.reset_on_present {
+            self.reset_style();
+        }
+
         Ok(())
     }
 
@@ -134,7 +3063,25 @@ impl<'a> DrawingBackend for SkiaBackend<'a> {
         point: BackendCoord,
         color: BackendColor,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        self.canvas.draw_point(point, &self.paint(color));
+        let key = PixelPaintKey {
+            color: skia_color(color),
+            blend_mode: self.style.blend_mode,
+            dither: self.dither,
+        };
+
+        let paint = match self.pixel_paint.take() {
+            Some((cached, paint)) if cached == key => paint,
+            _ => Self::pixel_paint(self.paint(color)),
+        };
+
+        // Centered on the pixel so it lights exactly that one
+        let center = (point.0 as f32 + 0.5, point.1 as f32 + 0.5);
+
+        for canvas in self.canvases() {
+            canvas.draw_point(center, &paint);
+        }
+
+        self.pixel_paint = Some((key, paint));
 
         Ok(())
     }
@@ -146,13 +3093,30 @@ impl<'a> DrawingBackend for SkiaBackend<'a> {
         to: BackendCoord,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let mut paint = self.paint(style.color());
+        let paint = self.style_paint(style, false);
+        let from = (from.0 as f32, from.1 as f32);
+        let to = (to.0 as f32, to.1 as f32);
+
+        let (from, to, canvas_clip) = match self.line_clip_bounds {
+            Some(bounds) if self.style.dash.is_none() => match clip_segment(bounds, from, to) {
+                Some((from, to)) => (from, to, None),
+                None => return Ok(()),
+            },
+            bounds => (from, to, bounds),
+        };
+
+        for canvas in self.canvases() {
+            if let Some(bounds) = canvas_clip {
+                canvas.save();
+                canvas.clip_rect(bounds, ClipOp::Intersect, false);
+            }
 
-        paint
-            .set_stroke_width(style.stroke_width() as f32)
-            .set_anti_alias(true);
+            canvas.draw_line(from, to, &paint);
 
-        self.canvas.draw_line(from, to, &paint);
+            if canvas_clip.is_some() {
+                canvas.restore();
+            }
+        }
 
         Ok(())
     }
@@ -164,17 +3128,7 @@ impl<'a> DrawingBackend for SkiaBackend<'a> {
         style: &S,
         fill: bool,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let mut paint = self.paint(style.color());
-
-        paint
-            .set_stroke_width(style.stroke_width() as f32)
-            .set_anti_alias(true);
-
-        if fill {
-            paint.set_style(PaintStyle::Fill);
-        } else {
-            paint.set_style(PaintStyle::Stroke);
-        }
+        let paint = self.shape_paint(style, fill);
 
         let rect = Rect::new(
             upper_left.0 as f32,
@@ -183,7 +3137,9 @@ impl<'a> DrawingBackend for SkiaBackend<'a> {
             bottom_right.1 as f32,
         );
 
-        self.canvas.draw_rect(rect, &paint);
+        for canvas in self.canvases() {
+            canvas.draw_rect(rect, &paint);
+        }
 
         Ok(())
     }
@@ -205,20 +3161,12 @@ impl<'a> DrawingBackend for SkiaBackend<'a> {
         style: &S,
         fill: bool,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let mut paint = self.paint(style.color());
+        let paint = self.shape_paint(style, fill);
 
-        paint
-            .set_stroke_width(style.stroke_width() as f32)
-            .set_anti_alias(true);
-
-        if fill {
-            paint.set_style(PaintStyle::Fill);
-        } else {
-            paint.set_style(PaintStyle::Stroke);
+        for canvas in self.canvases() {
+            canvas.draw_circle(center, radius as f32, &paint);
         }
 
-        self.canvas.draw_circle(center, radius as f32, &paint);
-
         Ok(())
     }
 
@@ -238,95 +3186,640 @@ impl<'a> DrawingBackend for SkiaBackend<'a> {
         (iw, ih): (u32, u32),
         src: &[u8],
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let info = ImageInfo::new(
-            (iw as i32, ih as i32),
-            // Data has to be provided as an RGBA image buffer
-            ColorType::RGBA8888,
-            AlphaType::Opaque,
-            None,
+        // Skia can't create empty images and there'd be nothing to draw anyway
+        if iw == 0 || ih == 0 {
+            return Ok(());
+        }
+
+        let img = self
+            .raster_image((iw, ih), src, self.bitmap_color_type, None)
+            .map_err(DrawingErrorKind::DrawingError)?;
+
+        for canvas in self.canvases() {
+            canvas.draw_image(&img, pos, None);
+        }
+
+        Ok(())
+    }
+
+    fn draw_text<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: BackendCoord,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let angle = match style.transform() {
+            FontTransform::None => 0.0,
+            FontTransform::Rotate90 => 90.0,
+            FontTransform::Rotate180 => 180.0,
+            FontTransform::Rotate270 => 270.0,
+        };
+
+        self.draw_text_angled(text, style, pos, angle)
+    }
+
+    fn estimate_text_size<TStyle: BackendTextStyle>(
+        &self,
+        text: &str,
+        style: &TStyle,
+    ) -> Result<(u32, u32), DrawingErrorKind<Self::ErrorType>> {
+        let font_size = style.size() as f32 * FONT_EM_FACTOR;
+        let (width, height) = self
+            .text_extent(text, style, font_size)
+            .map_err(DrawingErrorKind::DrawingError)?;
+
+        let size = (width.ceil() as u32, height.ceil() as u32);
+
+        match style.transform() {
+            FontTransform::Rotate90 | FontTransform::Rotate270 => Ok((size.1, size.0)),
+            FontTransform::None | FontTransform::Rotate180 => Ok(size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plotters::style::{IntoFont, TextStyle};
+    use skia_safe::surfaces;
+
+    use super::*;
+
+    const RED: BackendColor = BackendColor {
+        alpha: 1.0,
+        rgb: (255, 0, 0),
+    };
+
+    const BLUE: BackendColor = BackendColor {
+        alpha: 1.0,
+        rgb: (0, 0, 255),
+    };
+
+    // Registered by text tests so they don't depend on the system's fonts
+    const FONT: &[u8] = include_bytes!("../tests/fonts/DejaVuSansMono.ttf");
+    const FONT_FAMILY: &str = "DejaVu Sans Mono";
+
+    const OPAQUE_RED: [u8; 4] = [255, 0, 0, 255];
+    const OPAQUE_BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    struct Stroke(BackendColor, u32);
+
+    impl BackendStyle for Stroke {
+        fn color(&self) -> BackendColor {
+            self.0
+        }
+
+        fn stroke_width(&self) -> u32 {
+            self.1
+        }
+    }
+
+    // Straight alpha RGBA pixels read back from a surface
+    struct Pixels {
+        width: usize,
+        data: Vec<u8>,
+    }
+
+    impl Pixels {
+        fn read(surface: &mut Surface) -> Self {
+            let (width, height) = (surface.width() as usize, surface.height() as usize);
+            let info = ImageInfo::new(
+                (width as i32, height as i32),
+                ColorType::RGBA8888,
+                AlphaType::Unpremul,
+                None,
+            );
+
+            let mut data = vec![0; width * height * 4];
+            assert!(surface.read_pixels(&info, &mut data, width * 4, (0, 0)));
+
+            Self { width, data }
+        }
+
+        fn get(&self, x: usize, y: usize) -> [u8; 4] {
+            let i = (y * self.width + x) * 4;
+
+            self.data[i..i + 4].try_into().unwrap()
+        }
+
+        fn alphas(&self) -> impl Iterator<Item = u8> + '_ {
+            self.data.chunks_exact(4).map(|px| px[3])
+        }
+    }
+
+    fn render(w: u32, h: u32, f: impl FnOnce(&mut SkiaBackend<'_>)) -> Pixels {
+        let mut surface = surfaces::raster_n32_premul((w as i32, h as i32)).unwrap();
+        surface.canvas().clear(Color::TRANSPARENT);
+        f(&mut SkiaBackend::new(surface.canvas(), w, h));
+
+        Pixels::read(&mut surface)
+    }
+
+    #[test]
+    fn polygon_has_fill_and_border() {
+        let pixels = render(20, 20, |backend| {
+            let square = [(4, 4), (16, 4), (16, 16), (4, 16)];
+            backend.draw_polygon(square, &BLUE, &Stroke(RED, 2));
+        });
+
+        assert_eq!(pixels.get(10, 10), OPAQUE_BLUE);
+        assert_eq!(pixels.get(4, 10), OPAQUE_RED);
+        assert_eq!(pixels.get(0, 0), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn style_scope_restores_style() {
+        let mut surface = surfaces::raster_n32_premul((4, 4)).unwrap();
+        let mut backend = SkiaBackend::new(surface.canvas(), 4, 4);
+        backend.set_dash_scale(2.0);
+
+        backend.with_style_scope(|backend| {
+            backend
+                .set_blend_mode(Some(BlendMode::Multiply))
+                .set_dash_scale(3.0);
+
+            backend.draw_rect((0, 0), (4, 4), &RED, true).unwrap();
+        });
+
+        assert!(backend.style.blend_mode.is_none());
+        assert_eq!(backend.style.dash_scale, 2.0);
+    }
+
+    #[test]
+    fn round_capped_dashes_are_pills() {
+        let pixels = render(40, 20, |backend| {
+            backend.set_dash_with_cap(&[6.0, 14.0], 0.0, PaintCap::Round);
+            backend
+                .draw_line((0, 10), (40, 10), &Stroke(RED, 6))
+                .unwrap();
+        });
+
+        // Body of the first dash, which spans 0 to 6 plus a 3px cap on each side
+        assert_eq!(pixels.get(3, 9), OPAQUE_RED);
+
+        // Next to the cap's center it's covered but its corner is rounded off
+        assert!(pixels.get(8, 9)[3] > 192);
+        assert!(pixels.get(8, 7)[3] < 64);
+
+        // Gap up to the next dash's cap at 17
+        assert_eq!(pixels.get(13, 10)[3], 0);
+    }
+
+    #[test]
+    fn text_stays_anti_aliased() {
+        let pixels = render(80, 60, |backend| {
+            let style = TextStyle::from((FONT_FAMILY, 48).into_font());
+
+            backend
+                .register_typeface(FONT)
+                .unwrap()
+                .set_anti_alias(false);
+            backend.draw_text("Ag", &style, (10, 10)).unwrap();
+        });
+
+        assert!(pixels.alphas().any(|alpha| alpha > 0 && alpha < 255));
+    }
+
+    #[test]
+    fn clear_rect_to_transparent() {
+        let pixels = render(20, 20, |backend| {
+            backend.draw_rect((0, 0), (20, 20), &RED, true).unwrap();
+            backend.clear_rect((5, 5), (15, 15), None);
+        });
+
+        assert_eq!(pixels.get(10, 10), [0, 0, 0, 0]);
+        assert_eq!(pixels.get(2, 2), OPAQUE_RED);
+    }
+
+    #[test]
+    fn area_under_curve_is_filled() {
+        let pixels = render(30, 20, |backend| {
+            let curve = [(0, 4), (10, 4), (20, 16), (30, 16)];
+            backend.fill_under_curve(&curve, 10, &BLUE);
+        });
+
+        // Above the baseline where the curve is above it and below where it's below
+        assert_eq!(pixels.get(5, 7), OPAQUE_BLUE);
+        assert_eq!(pixels.get(25, 13), OPAQUE_BLUE);
+
+        assert_eq!(pixels.get(5, 2)[3], 0);
+        assert_eq!(pixels.get(5, 12)[3], 0);
+        assert_eq!(pixels.get(25, 7)[3], 0);
+    }
+
+    #[test]
+    fn erased_shapes_become_transparent() {
+        let pixels = render(40, 20, |backend| {
+            backend.draw_rect((0, 0), (40, 20), &RED, true).unwrap();
+            backend.erase_circle((10, 10), 6.0);
+            backend.erase_rect(Rect::new(25.0, 5.0, 35.0, 15.0));
+        });
+
+        assert_eq!(pixels.get(10, 10), [0, 0, 0, 0]);
+        assert_eq!(pixels.get(30, 10), [0, 0, 0, 0]);
+        assert_eq!(pixels.get(20, 10), OPAQUE_RED);
+        assert_eq!(pixels.get(10, 1), OPAQUE_RED);
+    }
+
+    #[test]
+    fn variable_width_path_widens() {
+        let pixels = render(40, 20, |backend| {
+            backend.draw_variable_width_path(&[((0, 10), 2.0), ((40, 10), 18.0)], BLUE);
+        });
+
+        // About 4px wide at x = 5 and 16px at x = 35
+        assert_eq!(pixels.get(5, 10), OPAQUE_BLUE);
+        assert_eq!(pixels.get(5, 4)[3], 0);
+        assert_eq!(pixels.get(35, 4), OPAQUE_BLUE);
+        assert_eq!(pixels.get(35, 0)[3], 0);
+    }
+
+    #[test]
+    fn contour_band_has_holes() {
+        let pixels = render(40, 20, |backend| {
+            let outer = [(0, 0), (40, 0), (40, 20), (0, 20)];
+
+            // Opposite windings since even-odd filling shouldn't care
+            let holes = [
+                vec![(5, 5), (15, 5), (15, 15), (5, 15)],
+                vec![(25, 5), (25, 15), (35, 15), (35, 5)],
+            ];
+
+            backend.fill_contour_band(&outer, &holes, BLUE);
+        });
+
+        assert_eq!(pixels.get(20, 10), OPAQUE_BLUE);
+        assert_eq!(pixels.get(2, 2), OPAQUE_BLUE);
+        assert_eq!(pixels.get(10, 10)[3], 0);
+        assert_eq!(pixels.get(30, 10)[3], 0);
+    }
+
+    #[test]
+    fn estimated_text_width_matches_drawn_extent() {
+        let style = TextStyle::from((FONT_FAMILY, 32).into_font());
+        let mut estimated = 0;
+
+        let pixels = render(200, 60, |backend| {
+            backend.register_typeface(FONT).unwrap();
+            estimated = backend.estimate_text_size("HHHH", &style).unwrap().0;
+            backend.draw_text("HHHH", &style, (10, 10)).unwrap();
+        });
+
+        let height = pixels.data.len() / 4 / pixels.width;
+        let right = (0..pixels.width)
+            .rev()
+            .find(|&x| (0..height).any(|y| pixels.get(x, y)[3] > 0))
+            .unwrap();
+
+        // The advance width also covers the last glyph's right side bearing
+        let drawn = (right + 1 - 10) as i32;
+        assert!(
+            (estimated as i32 - drawn).abs() <= 4,
+            "{estimated} vs {drawn}"
         );
+    }
+
+    fn assert_close(actual: [u8; 4], expected: [u8; 4]) {
+        let close = actual
+            .iter()
+            .zip(expected)
+            .all(|(a, e)| (*a as i32 - e as i32).abs() <= 2);
 
-        // SAFETY: `src` outlives `data`
-        let data = unsafe { Data::new_bytes(src) };
-        let row_bytes = iw * 4;
+        assert!(close, "{actual:?} isn't close to {expected:?}");
+    }
 
-        let img = images::raster_from_data(&info, data, row_bytes as usize)
-            .ok_or(DrawingErrorKind::DrawingError(SkiaError::ImageFromRaster))?;
+    #[test]
+    fn blit_bitmap_blends_straight_alpha() {
+        let white = BackendColor {
+            alpha: 1.0,
+            rgb: (255, 255, 255),
+        };
+
+        #[rustfmt::skip]
+        let src = [
+            255, 0, 0, 255,   0, 0, 255, 128,
+            0, 0, 0, 0,       0, 255, 0, 255,
+        ];
+
+        let pixels = render(2, 2, |backend| {
+            backend.draw_rect((0, 0), (2, 2), &white, true).unwrap();
+            backend.blit_bitmap((0, 0), (2, 2), &src).unwrap();
+        });
+
+        assert_eq!(pixels.get(0, 0), OPAQUE_RED);
+        assert_close(pixels.get(1, 0), [127, 127, 255, 255]);
+        assert_eq!(pixels.get(0, 1), [255, 255, 255, 255]);
+        assert_eq!(pixels.get(1, 1), [0, 255, 0, 255]);
+    }
 
-        self.canvas.draw_image(img, pos, None);
+    #[test]
+    fn ellipse_has_fill_and_stroke() {
+        let pixels = render(40, 20, |backend| {
+            backend.draw_ellipse_filled_stroked((20, 10), (15.0, 8.0), BLUE, RED, 2.0);
+        });
 
-        Ok(())
+        assert_eq!(pixels.get(20, 10), OPAQUE_BLUE);
+        assert_close(pixels.get(34, 10), OPAQUE_RED);
+        assert_eq!(pixels.get(1, 1)[3], 0);
+    }
+
+    #[test]
+    fn undersized_bitmap_is_an_error() {
+        render(4, 4, |backend| {
+            let res = backend.blit_bitmap((0, 0), (2, 2), &[0; 15]);
+
+            assert!(matches!(
+                res,
+                Err(DrawingErrorKind::DrawingError(
+                    SkiaError::BitmapBufferTooSmall {
+                        expected: 16,
+                        got: 15
+                    }
+                ))
+            ));
+        });
+    }
+
+    #[test]
+    fn blit_bgra_bitmap() {
+        // One pixel per row, padded to 8 bytes
+        let src = [0, 0, 255, 255, 9, 9, 9, 9, 255, 0, 0, 255];
+
+        let pixels = render(2, 2, |backend| {
+            backend
+                .blit_bitmap_with_format((0, 0), (1, 2), &src, ColorType::BGRA8888, 8)
+                .unwrap();
+
+            backend.set_bitmap_color_type(ColorType::BGRA8888);
+            backend.blit_bitmap((1, 0), (1, 1), &src[8..]).unwrap();
+        });
+
+        assert_eq!(pixels.get(0, 0), OPAQUE_RED);
+        assert_eq!(pixels.get(0, 1), OPAQUE_BLUE);
+        assert_eq!(pixels.get(1, 0), OPAQUE_BLUE);
+    }
+
+    #[test]
+    fn clip_rect_keeps_outside_untouched() {
+        let pixels = render(20, 20, |backend| {
+            backend.push_clip_rect((5, 5), (15, 15));
+            backend
+                .draw_line((0, 10), (20, 10), &Stroke(RED, 2))
+                .unwrap();
+            backend.pop_clip();
+        });
+
+        assert_eq!(pixels.get(10, 9), OPAQUE_RED);
+        assert_eq!(pixels.get(2, 9), [0, 0, 0, 0]);
+        assert_eq!(pixels.get(17, 10), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn aliased_diagonal_line() {
+        let pixels = render(20, 20, |backend| {
+            backend.set_anti_alias(false);
+            backend.draw_line((0, 0), (20, 20), &RED).unwrap();
+        });
+
+        // Without coverage blending pixels are either fully drawn or left untouched
+        assert!(pixels.alphas().all(|alpha| alpha == 0 || alpha == 255));
+        assert!(pixels.alphas().any(|alpha| alpha == 255));
+        assert_eq!(pixels.get(15, 2), [0, 0, 0, 0]);
+        assert_eq!(pixels.get(2, 15), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn registered_typeface_draws_text() {
+        let style = TextStyle::from((FONT_FAMILY, 20).into_font());
+
+        let pixels = render(100, 40, |backend| {
+            backend.register_typeface(FONT).unwrap();
+
+            // Resolved from the registered typefaces, not the system's
+            let typeface = backend.typeface(&style).unwrap();
+            assert_eq!(typeface.unique_id(), backend.typefaces[0].unique_id());
+
+            assert!(backend.estimate_text_size("42", &style).unwrap().0 > 0);
+            backend.draw_text("42", &style, (5, 5)).unwrap();
+        });
+
+        assert!(pixels.alphas().any(|alpha| alpha > 0));
+    }
+
+    #[test]
+    fn dashed_line_alternates_runs() {
+        let pixels = render(32, 20, |backend| {
+            backend.set_dash(&[4.0, 4.0], 0.0);
+            backend
+                .draw_line((0, 10), (32, 10), &Stroke(RED, 2))
+                .unwrap();
+        });
+
+        for x in 0..32 {
+            let expected = if x % 8 < 4 { OPAQUE_RED } else { [0, 0, 0, 0] };
+
+            assert_eq!(pixels.get(x, 9), expected, "x = {x}");
+        }
+    }
+
+    #[test]
+    fn cached_pixel_paint_matches_draw_point() {
+        let pixels: Vec<_> = (0..10_000)
+            .map(|i| {
+                let point = (i % 100, i / 100);
+
+                // Runs of equal colors hit the cached paint, color changes miss it
+                let shade = (i / 7 % 256) as u8;
+                let color = BackendColor {
+                    alpha: 1.0,
+                    rgb: (shade, 255 - shade, 128),
+                };
+
+                (point, color)
+            })
+            .collect();
+
+        let drawn = render(100, 100, |backend| {
+            for &(point, color) in &pixels {
+                backend.draw_pixel(point, color).unwrap();
+            }
+        });
+
+        let expected = render(100, 100, |backend| {
+            for &(point, color) in &pixels {
+                let paint = SkiaBackend::pixel_paint(backend.paint(color));
+                let center = (point.0 as f32 + 0.5, point.1 as f32 + 0.5);
+                backend.canvas_mut().draw_point(center, &paint);
+            }
+        });
+
+        assert!(drawn.data == expected.data);
+    }
+
+    #[test]
+    fn scaled_line_covers_device_pixels() {
+        let mut surface = surfaces::raster_n32_premul((40, 40)).unwrap();
+        surface.canvas().clear(Color::TRANSPARENT);
+
+        {
+            let mut backend = SkiaBackend::with_scale(surface.canvas(), 40, 40, 2.0);
+            assert_eq!(backend.get_size(), (20, 20));
+
+            backend.draw_line((0, 5), (10, 5), &Stroke(RED, 2)).unwrap();
+        }
+
+        let pixels = Pixels::read(&mut surface);
+        let covered = (0..40).filter(|&x| pixels.get(x, 10)[3] > 0).count();
+
+        assert_eq!(covered, 20);
+    }
+
+    #[test]
+    fn from_surface_presents() {
+        let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+        surface.canvas().clear(Color::TRANSPARENT);
+
+        {
+            let mut backend = SkiaBackend::from_surface(&mut surface);
+            backend.draw_rect((2, 2), (8, 8), &BLUE, true).unwrap();
+            backend.present().unwrap();
+        }
+
+        let pixels = Pixels::read(&mut surface);
+
+        assert_eq!(pixels.get(5, 5), OPAQUE_BLUE);
+        assert_eq!(pixels.get(0, 0), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn rect_with_linear_gradient() {
+        let pixels = render(10, 200, |backend| {
+            backend.set_linear_gradient((0, 0), (0, 200), &[(0.0, RED), (1.0, BLUE)]);
+            backend.draw_rect((0, 0), (10, 200), &RED, true).unwrap();
+            backend.clear_shader();
+        });
+
+        for x in 0..10 {
+            assert_close(pixels.get(x, 0), OPAQUE_RED);
+            assert_close(pixels.get(x, 199), OPAQUE_BLUE);
+        }
+
+        assert_close(pixels.get(5, 100), [127, 0, 128, 255]);
+    }
+
+    #[test]
+    fn textured_fill_stays_inside_polygon() {
+        let info = ImageInfo::new((2, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let texture =
+            images::raster_from_data(&info, Data::new_copy(&[0, 255, 0, 255].repeat(4)), 8)
+                .unwrap();
+
+        let pixels = render(20, 20, |backend| {
+            let triangle = [(0, 0), (20, 0), (0, 20)];
+            backend.fill_polygon_textured(triangle, &texture, Matrix::scale((5.0, 5.0)));
+        });
+
+        assert_eq!(pixels.get(3, 3), [0, 255, 0, 255]);
+        assert_eq!(pixels.get(16, 16), [0, 0, 0, 0]);
+        assert_eq!(pixels.get(12, 12), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn step_path_through_three_points() {
+        let points = [(2, 15), (10, 5), (18, 15)];
+
+        let after = render(20, 20, |backend| {
+            backend.draw_step_path(points, &Stroke(RED, 2), false);
+        });
+
+        // Along y = 15, up x = 10, along y = 5 and down x = 18
+        assert_eq!(after.get(6, 14), OPAQUE_RED);
+        assert_eq!(after.get(9, 10), OPAQUE_RED);
+        assert_eq!(after.get(14, 4), OPAQUE_RED);
+        assert_eq!(after.get(17, 10), OPAQUE_RED);
+        assert_eq!(after.get(6, 4), [0, 0, 0, 0]);
+        assert_eq!(after.get(14, 14), [0, 0, 0, 0]);
+
+        let before = render(20, 20, |backend| {
+            backend.draw_step_path(points, &Stroke(RED, 2), true);
+        });
+
+        // Up x = 2, along y = 5, down x = 10 and along y = 15
+        assert_eq!(before.get(1, 10), OPAQUE_RED);
+        assert_eq!(before.get(6, 4), OPAQUE_RED);
+        assert_eq!(before.get(14, 14), OPAQUE_RED);
+        assert_eq!(before.get(6, 14), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn draw_pixel_lights_one_pixel() {
+        let pixels = render(8, 8, |backend| {
+            backend.draw_pixel((3, 3), RED).unwrap();
+        });
+
+        for y in 0..8 {
+            for x in 0..8 {
+                let expected = if (x, y) == (3, 3) {
+                    OPAQUE_RED
+                } else {
+                    [0, 0, 0, 0]
+                };
+
+                assert_eq!(pixels.get(x, y), expected, "({x}, {y})");
+            }
+        }
     }
 
-    // Couldn't get font drawing to match the original close enough so it's just using the default implementation for text.
-    // Much less efficient since it uses draw_pixel internally which is a shame but owell.
-
-    // fn draw_text<TStyle: BackendTextStyle>(
-    //     &mut self,
-    //     text: &str,
-    //     style: &TStyle,
-    //     pos: BackendCoord,
-    // ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-    //     let paint = Self::paint(style.color());
-    //     let font = Self::font(style).map_err(DrawingErrorKind::DrawingError)?;
-
-    //     let (width, rect) = font.measure_str(text, Some(&paint));
-    //     let height = rect.height();
-
-    //     let dx = match style.anchor().h_pos {
-    //         HPos::Left => 0.0,
-    //         HPos::Right => -width,
-    //         HPos::Center => -width / 2.0,
-    //     };
-
-    //     let dy = match style.anchor().v_pos {
-    //         VPos::Top => height,
-    //         VPos::Center => height / 2.0,
-    //         VPos::Bottom => 0.0,
-    //     };
-
-    //     let anchored_pos = (pos.0 as f32 + dx, pos.1 as f32 + dy - 1.0);
-
-    //     match style.transform() {
-    //         FontTransform::None => {}
-    //         FontTransform::Rotate90 => {
-    //             self.canvas.rotate(90.0, Some(pos.into()));
-    //         }
-    //         FontTransform::Rotate180 => {
-    //             self.canvas.rotate(180.0, Some(pos.into()));
-    //         }
-    //         FontTransform::Rotate270 => {
-    //             self.canvas.rotate(270.0, Some(pos.into()));
-    //         }
-    //     }
-
-    //     self.canvas.draw_str(text, anchored_pos, &font, &paint);
-
-    //     match style.transform() {
-    //         FontTransform::None => {}
-    //         FontTransform::Rotate90 => {
-    //             self.canvas.rotate(-90.0, Some(pos.into()));
-    //         }
-    //         FontTransform::Rotate180 => {
-    //             self.canvas.rotate(-180.0, Some(pos.into()));
-    //         }
-    //         FontTransform::Rotate270 => {
-    //             self.canvas.rotate(-270.0, Some(pos.into()));
-    //         }
-    //     }
-
-    //     Ok(())
-    // }
-
-    // fn estimate_text_size<TStyle: BackendTextStyle>(
-    //     &self,
-    //     text: &str,
-    //     style: &TStyle,
-    // ) -> Result<(u32, u32), DrawingErrorKind<Self::ErrorType>> {
-    //     let paint = Self::paint(style.color());
-    //     let font = Self::font(style).map_err(DrawingErrorKind::DrawingError)?;
-    //     let (_, rect) = font.measure_str(text, Some(&paint));
-
-    //     Ok((rect.width() as u32, rect.height() as u32))
-    // }
+    #[test]
+    fn fitted_text_stays_inside_box() {
+        let box_rect = Rect::new(10.0, 10.0, 110.0, 40.0);
+
+        let pixels = render(120, 60, |backend| {
+            let style = TextStyle::from((FONT_FAMILY, 100).into_font())
+                .pos(Pos::new(HPos::Left, VPos::Top));
+
+            backend.register_typeface(FONT).unwrap();
+            let size = backend.draw_text_fit("Hello", &style, box_rect).unwrap();
+            assert!(size < 100.0);
+        });
+
+        for y in 0..60 {
+            for x in 0..120 {
+                let inside = (10..110).contains(&x) && (10..40).contains(&y);
+
+                if !inside {
+                    assert_eq!(pixels.get(x, y)[3], 0, "({x}, {y})");
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "shaping")]
+    #[test]
+    fn measures_families_only_in_the_font_collection() {
+        use skia_safe::textlayout::TypefaceFontProvider;
+
+        let typeface = FontMgr::new().new_from_data(FONT, None).unwrap();
+        let mut provider = TypefaceFontProvider::new();
+        provider.register_typeface(typeface, Some("Collection Only"));
+
+        let mut collection = FontCollection::new();
+        collection.set_asset_font_manager(Some(provider.into()));
+
+        let style = TextStyle::from(("Collection Only", 20).into_font());
+
+        render(100, 40, |backend| {
+            backend.set_font_collection(collection);
+
+            let (width, height) = backend.estimate_text_size("42", &style).unwrap();
+            assert!(width > 0 && height > 0);
+
+            let placed = backend
+                .draw_labels_no_overlap(&[((5, 5), "42".to_owned())], &style)
+                .unwrap();
+            assert_eq!(placed, [true]);
+
+            let fit_rect = Rect::new(0.0, 20.0, 40.0, 30.0);
+            assert!(backend.draw_text_fit("42", &style, fit_rect).unwrap() < 20.0);
+        });
+    }
 }
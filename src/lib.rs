@@ -1,3 +1,11 @@
 mod backend;
+mod number_format;
+mod pixels;
+mod surface;
+mod tile_cache;
 
-pub use backend::SkiaBackend;
+pub use backend::{AaMethod, MarkerShape, Orientation, RoundingMode, SkiaBackend, SkiaError};
+pub use number_format::NumberFormat;
+pub use pixels::{premultiply_rgba, unpremultiply_rgba};
+pub use surface::{encode_surface, render_to_png_file, ImageFormat, SkiaSurfaceBackend};
+pub use tile_cache::{SkiaTileCache, TileKey, Viewport};
@@ -0,0 +1,52 @@
+/// How [`SkiaBackend::draw_number`](crate::SkiaBackend::draw_number) formats its value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Digits after the decimal point.
+    pub decimals: usize,
+    /// Inserted between groups of three integer digits, e.g. `1,234,567`.
+    pub grouping: Option<char>,
+    pub decimal_point: char,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimals: 0,
+            grouping: Some(','),
+            decimal_point: '.',
+        }
+    }
+}
+
+impl NumberFormat {
+    pub fn format(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+
+        let digits = format!("{:.*}", self.decimals, value.abs());
+        let (int, frac) = digits.split_once('.').unwrap_or((&digits, ""));
+
+        let mut out = String::with_capacity(digits.len() + int.len() / 3 + 1);
+
+        // Rounding may have turned e.g. -0.001 into "0"
+        if value.is_sign_negative() && digits.bytes().any(|b| (b'1'..=b'9').contains(&b)) {
+            out.push('-');
+        }
+
+        for (i, digit) in int.chars().enumerate() {
+            if let Some(sep) = self.grouping.filter(|_| i > 0 && (int.len() - i) % 3 == 0) {
+                out.push(sep);
+            }
+
+            out.push(digit);
+        }
+
+        if !frac.is_empty() {
+            out.push(self.decimal_point);
+            out.push_str(frac);
+        }
+
+        out
+    }
+}
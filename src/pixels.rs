@@ -0,0 +1,61 @@
+/// Convert an RGBA8888 buffer with straight alpha into premultiplied alpha in place.
+pub fn premultiply_rgba(buf: &mut [u8]) {
+    for px in buf.chunks_exact_mut(4) {
+        let alpha = px[3] as u32;
+
+        for channel in &mut px[..3] {
+            *channel = ((*channel as u32 * alpha + 127) / 255) as u8;
+        }
+    }
+}
+
+/// Convert an RGBA8888 buffer with premultiplied alpha into straight alpha in place.
+///
+/// Fully transparent pixels become transparent black.
+pub fn unpremultiply_rgba(buf: &mut [u8]) {
+    for px in buf.chunks_exact_mut(4) {
+        let alpha = px[3] as u32;
+
+        for channel in &mut px[..3] {
+            *channel = match alpha {
+                0 => 0,
+                _ => ((*channel as u32 * 255 + alpha / 2) / alpha).min(255) as u8,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn premultiply_round_trip() {
+        for alpha in 0..=255_u8 {
+            let mut buf: Vec<u8> = (0..=255).flat_map(|c| [c, 255 - c, c / 2, alpha]).collect();
+            let original = buf.clone();
+
+            premultiply_rgba(&mut buf);
+            unpremultiply_rgba(&mut buf);
+
+            // Premultiplying keeps only `alpha + 1` distinct levels per channel
+            let tolerance = match alpha {
+                0 => 0,
+                _ => 255 / (2 * alpha as i32) + 1,
+            };
+
+            for (px, orig) in buf.chunks_exact(4).zip(original.chunks_exact(4)) {
+                assert_eq!(px[3], orig[3]);
+
+                for (c, o) in px[..3].iter().zip(&orig[..3]) {
+                    let expected = if alpha == 0 { 0 } else { *o as i32 };
+
+                    assert!(
+                        (*c as i32 - expected).abs() <= tolerance,
+                        "{orig:?} became {px:?}"
+                    );
+                }
+            }
+        }
+    }
+}
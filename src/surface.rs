@@ -0,0 +1,338 @@
+use std::{
+    error::Error as StdError,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use plotters::{
+    coord::Shift,
+    drawing::{DrawingArea, IntoDrawingArea},
+};
+use skia_safe::{
+    jpeg_encoder, png_encoder, surfaces, AlphaType, ColorSpace, ColorType, EncodedImageFormat,
+    FilterMode, Image, ImageInfo, MipmapMode, Paint, Rect, SamplingOptions, Surface,
+};
+
+use crate::{SkiaBackend, SkiaError};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    /// JPEG with a quality from 0 to 100
+    Jpeg(u32),
+}
+
+impl ImageFormat {
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg(_) => "image/jpeg",
+        }
+    }
+
+    fn encoded_format(self) -> (EncodedImageFormat, Option<u32>) {
+        match self {
+            ImageFormat::Png => (EncodedImageFormat::PNG, None),
+            ImageFormat::Jpeg(quality) => (EncodedImageFormat::JPEG, Some(quality)),
+        }
+    }
+}
+
+/// Owns a raster [`Surface`] and hands out [`SkiaBackend`]s drawing into it.
+pub struct SkiaSurfaceBackend {
+    surface: Surface,
+    width: u32,
+    height: u32,
+    factor: u32,
+}
+
+impl SkiaSurfaceBackend {
+    pub fn new(w: u32, h: u32) -> Result<Self, SkiaError> {
+        let surface =
+            surfaces::raster_n32_premul((w as i32, h as i32)).ok_or(SkiaError::Surface)?;
+
+        Ok(Self {
+            surface,
+            width: w,
+            height: h,
+            factor: 1,
+        })
+    }
+
+    /// Render into a surface `factor` times larger in each dimension and downsample it to
+    /// `w` x `h` when exporting, for smoother thin curves and text than analytic AA alone.
+    ///
+    /// Backends still draw in `w` x `h` coordinates. Memory use and rendering time grow
+    /// with `factor²`, so factors beyond 2 to 4 are rarely worth it.
+    pub fn new_supersampled(w: u32, h: u32, factor: u32) -> Result<Self, SkiaError> {
+        let factor = factor.max(1);
+        let scaled = |len: u32| {
+            len.checked_mul(factor)
+                .and_then(|len| i32::try_from(len).ok())
+                .ok_or(SkiaError::Surface)
+        };
+
+        let size = (scaled(w)?, scaled(h)?);
+        let surface = surfaces::raster_n32_premul(size).ok_or(SkiaError::Surface)?;
+
+        Ok(Self {
+            surface,
+            width: w,
+            height: h,
+            factor,
+        })
+    }
+
+    /// Render into a surface with 16-bit float channels for high bit depth output, e.g. via
+    /// [`Self::encode_png_16`].
+    ///
+    /// Formats without high bit depth support, such as JPEG, are still encoded with 8 bits
+    /// per channel.
+    pub fn new_f16(w: u32, h: u32) -> Result<Self, SkiaError> {
+        let info = ImageInfo::new(
+            (w as i32, h as i32),
+            ColorType::RGBAF16,
+            AlphaType::Premul,
+            ColorSpace::new_srgb(),
+        );
+
+        let surface = surfaces::raster(&info, None, None).ok_or(SkiaError::Surface)?;
+
+        Ok(Self {
+            surface,
+            width: w,
+            height: h,
+            factor: 1,
+        })
+    }
+
+    pub fn backend(&mut self) -> SkiaBackend<'_> {
+        let canvas = self.surface.canvas();
+
+        if self.factor > 1 {
+            canvas.reset_matrix();
+            canvas.scale((self.factor as f32, self.factor as f32));
+        }
+
+        SkiaBackend::new(canvas, self.width, self.height)
+    }
+
+    pub fn surface_mut(&mut self) -> &mut Surface {
+        &mut self.surface
+    }
+
+    /// Snapshot of the surface, downsampled to the requested size when supersampling.
+    pub fn image_snapshot(&mut self) -> Image {
+        let image = self.surface.image_snapshot();
+
+        if self.factor == 1 {
+            return image;
+        }
+
+        let size = (self.width as i32, self.height as i32);
+        let info = self.surface.image_info().with_dimensions(size);
+
+        let Some(mut target) = surfaces::raster(&info, None, None) else {
+            return image;
+        };
+
+        // Mipmaps average the supersampled pixels instead of skipping most of them
+        let sampling = SamplingOptions::new(FilterMode::Linear, MipmapMode::Linear);
+        let dst = Rect::from_wh(self.width as f32, self.height as f32);
+
+        target.canvas().draw_image_rect_with_sampling_options(
+            &image,
+            None,
+            dst,
+            sampling,
+            &Paint::default(),
+        );
+
+        target.image_snapshot()
+    }
+
+    pub fn encode(&mut self, format: ImageFormat) -> Result<Vec<u8>, SkiaError> {
+        encode_image(&self.image_snapshot(), format)
+    }
+
+    pub fn encode_png(&mut self) -> Result<Vec<u8>, SkiaError> {
+        self.encode(ImageFormat::Png)
+    }
+
+    /// Encode the surface as a PNG with 16 bits per channel if it was created with
+    /// [`Self::new_f16`].
+    ///
+    /// Other surfaces, or pixels Skia's PNG encoder can't write at 16 bits, fall back to
+    /// [`Self::encode_png`].
+    pub fn encode_png_16(&mut self) -> Result<Vec<u8>, SkiaError> {
+        let image = self.image_snapshot();
+        let mut bytes = Vec::new();
+
+        let encoded = image.color_type() == ColorType::RGBAF16
+            && image.peek_pixels().is_some_and(|pixmap| {
+                png_encoder::encode(&pixmap, &mut bytes, &png_encoder::Options::default())
+            });
+
+        if encoded {
+            Ok(bytes)
+        } else {
+            self.encode_png()
+        }
+    }
+
+    /// Encode the surface's pixels straight into the file at `path`.
+    ///
+    /// Unlike [`Self::encode`] the encoded bytes are streamed to disk as they are produced
+    /// instead of being collected in memory first, which matters for very large surfaces.
+    pub fn encode_to_file_streaming<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        format: ImageFormat,
+    ) -> Result<(), SkiaError> {
+        // Supersampled surfaces have to be downsampled into a separate image first
+        let image;
+
+        let pixmap = if self.factor > 1 {
+            image = self.image_snapshot();
+            image.peek_pixels()
+        } else {
+            self.surface.peek_pixels()
+        };
+
+        let pixmap = pixmap.ok_or(SkiaError::Encode)?;
+
+        // Only created once there are pixels to encode so failures don't clobber the file
+        let path = path.as_ref();
+        let mut writer = BufWriter::new(File::create(path).map_err(SkiaError::Io)?);
+
+        let encoded = match format {
+            ImageFormat::Png => {
+                png_encoder::encode(&pixmap, &mut writer, &png_encoder::Options::default())
+            }
+            ImageFormat::Jpeg(quality) => {
+                let options = jpeg_encoder::Options {
+                    quality,
+                    ..jpeg_encoder::Options::default()
+                };
+
+                jpeg_encoder::encode(&pixmap, &mut writer, &options)
+            }
+        };
+
+        if !encoded {
+            drop(writer);
+            let _ = fs::remove_file(path);
+
+            return Err(SkiaError::Encode);
+        }
+
+        writer.flush().map_err(SkiaError::Io)
+    }
+
+    /// Encode the surface as a `data:` URI, e.g. for an `<img>` tag's `src`.
+    pub fn into_data_uri(&mut self, format: ImageFormat) -> Result<String, SkiaError> {
+        let bytes = self.encode(format)?;
+
+        Ok(format!(
+            "data:{};base64,{}",
+            format.mime_type(),
+            base64(&bytes)
+        ))
+    }
+}
+
+/// Encode a snapshot of the raster `surface`, e.g. one drawn into via
+/// [`SkiaBackend::from_surface`].
+///
+/// GPU-backed surfaces can't be encoded without their context and return
+/// [`SkiaError::Encode`]; read their pixels back into a raster image first.
+pub fn encode_surface(surface: &mut Surface, format: ImageFormat) -> Result<Vec<u8>, SkiaError> {
+    encode_image(&surface.image_snapshot(), format)
+}
+
+fn encode_image(image: &Image, format: ImageFormat) -> Result<Vec<u8>, SkiaError> {
+    let (format, quality) = format.encoded_format();
+    let data = image
+        .encode(None, format, quality)
+        .ok_or(SkiaError::Encode)?;
+
+    Ok(data.as_bytes().to_vec())
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0_u32, |n, (i, byte)| n | ((*byte as u32) << (16 - 8 * i)));
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
+
+/// Render a chart into a `width`x`height` PNG file at `path`.
+///
+/// The closure receives the root drawing area; it is presented once the closure returns.
+pub fn render_to_png_file<P: AsRef<Path>>(
+    path: P,
+    width: u32,
+    height: u32,
+    f: impl FnOnce(&DrawingArea<SkiaBackend<'_>, Shift>) -> Result<(), Box<dyn StdError>>,
+) -> Result<(), Box<dyn StdError>> {
+    let mut surface = SkiaSurfaceBackend::new(width, height)?;
+
+    {
+        let root = surface.backend().into_drawing_area();
+        f(&root)?;
+        root.present()?;
+    }
+
+    fs::write(path, surface.encode_png()?)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use skia_safe::Data;
+
+    use super::*;
+
+    const PNG_MAGIC: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+    fn assert_png(bytes: &[u8], width: i32, height: i32) {
+        assert!(bytes.starts_with(PNG_MAGIC));
+
+        let image = Image::from_encoded(Data::new_copy(bytes)).unwrap();
+        assert_eq!((image.width(), image.height()), (width, height));
+    }
+
+    #[test]
+    fn encodes_png() {
+        let mut surface = SkiaSurfaceBackend::new(30, 20).unwrap();
+        assert_png(&surface.encode_png().unwrap(), 30, 20);
+
+        let mut supersampled = SkiaSurfaceBackend::new_supersampled(30, 20, 2).unwrap();
+        assert_png(&supersampled.encode_png().unwrap(), 30, 20);
+
+        let mut raster = surfaces::raster_n32_premul((12, 8)).unwrap();
+        assert_png(
+            &encode_surface(&mut raster, ImageFormat::Png).unwrap(),
+            12,
+            8,
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use plotters_backend::DrawingBackend;
+use skia_safe::{surfaces, Image};
+
+use crate::{SkiaBackend, SkiaError};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TileKey {
+    pub x: i32,
+    pub y: i32,
+    pub zoom: u32,
+}
+
+/// Top-left corner of the visible area in zoomed content pixels, plus the zoom level.
+#[derive(Copy, Clone, Debug)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub zoom: u32,
+}
+
+struct CachedTile {
+    image: Image,
+    last_used: u64,
+}
+
+/// Caches rendered square tiles of a chart so pan/zoom frames only render what is new.
+///
+/// Each tile costs `tile_size * tile_size * 4` bytes. After every frame the least recently
+/// drawn tiles are evicted until the cache fits `memory_budget`; tiles visible in the
+/// current frame are never evicted, so a viewport needing more tiles than the budget
+/// allows will temporarily exceed it.
+pub struct SkiaTileCache {
+    tile_size: u32,
+    memory_budget: usize,
+    tiles: HashMap<TileKey, CachedTile>,
+    frame: u64,
+}
+
+impl SkiaTileCache {
+    pub fn new(tile_size: u32, memory_budget: usize) -> Self {
+        Self {
+            tile_size,
+            memory_budget,
+            tiles: HashMap::new(),
+            frame: 0,
+        }
+    }
+
+    pub fn memory_used(&self) -> usize {
+        self.tiles.len() * self.tile_bytes()
+    }
+
+    /// Drop all cached tiles, e.g. after the underlying data changed.
+    pub fn invalidate(&mut self) {
+        self.tiles.clear();
+    }
+
+    /// Composite the tiles covering `viewport` onto `backend`, rendering missing ones.
+    ///
+    /// `render` receives a tile-sized backend whose canvas is translated so that drawing
+    /// in zoomed content coordinates lands on the requested tile.
+    pub fn draw_visible<F>(
+        &mut self,
+        backend: &mut SkiaBackend<'_>,
+        viewport: Viewport,
+        mut render: F,
+    ) -> Result<(), SkiaError>
+    where
+        F: FnMut(&mut SkiaBackend<'_>, TileKey),
+    {
+        self.frame += 1;
+
+        let size = self.tile_size as f32;
+        let (w, h) = backend.get_size();
+
+        let x0 = (viewport.x / size).floor() as i32;
+        let y0 = (viewport.y / size).floor() as i32;
+        let x1 = ((viewport.x + w as f32) / size).ceil() as i32;
+        let y1 = ((viewport.y + h as f32) / size).ceil() as i32;
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let key = TileKey {
+                    x,
+                    y,
+                    zoom: viewport.zoom,
+                };
+
+                if !self.tiles.contains_key(&key) {
+                    let image = self.render_tile(key, &mut render)?;
+
+                    self.tiles.insert(
+                        key,
+                        CachedTile {
+                            image,
+                            last_used: 0,
+                        },
+                    );
+                }
+
+                if let Some(tile) = self.tiles.get_mut(&key) {
+                    tile.last_used = self.frame;
+
+                    let pos = (x as f32 * size - viewport.x, y as f32 * size - viewport.y);
+
+                    for canvas in backend.canvases() {
+                        canvas.draw_image(&tile.image, pos, None);
+                    }
+                }
+            }
+        }
+
+        self.evict();
+
+        Ok(())
+    }
+
+    fn tile_bytes(&self) -> usize {
+        self.tile_size as usize * self.tile_size as usize * 4
+    }
+
+    fn render_tile<F>(&self, key: TileKey, render: &mut F) -> Result<Image, SkiaError>
+    where
+        F: FnMut(&mut SkiaBackend<'_>, TileKey),
+    {
+        let size = self.tile_size as i32;
+        let mut surface = surfaces::raster_n32_premul((size, size)).ok_or(SkiaError::Surface)?;
+
+        let canvas = surface.canvas();
+        canvas.translate(((-key.x * size) as f32, (-key.y * size) as f32));
+        render(
+            &mut SkiaBackend::new(canvas, self.tile_size, self.tile_size),
+            key,
+        );
+
+        Ok(surface.image_snapshot())
+    }
+
+    fn evict(&mut self) {
+        while self.memory_used() > self.memory_budget {
+            let oldest = self
+                .tiles
+                .iter()
+                .filter(|(_, tile)| tile.last_used < self.frame)
+                .min_by_key(|(_, tile)| tile.last_used)
+                .map(|(key, _)| *key);
+
+            match oldest {
+                Some(key) => self.tiles.remove(&key),
+                None => break,
+            };
+        }
+    }
+}